@@ -1,11 +1,32 @@
+use crate::named_colors::named_color;
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
+/// Color of a fill or a stroke.
+///
+/// Colors can be parsed from CSS color strings, e.g. from configuration files:
+///
+/// ```
+/// # use geo_svg::Color;
+/// assert_eq!("#ff8000".parse(), Ok(Color::Hex(0xFF8000)));
+/// assert_eq!("rgba(255, 128, 0, 0.5)".parse(), Ok(Color::Rgba(255, 128, 0, 0.5)));
+/// assert_eq!("Teal".parse(), Ok(Color::Named("teal")));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Color {
+    /// CSS named color, written as is; use [`Color::named`] or the constants like [`Color::RED`]
+    /// to make sure the name exists
     Named(&'static str),
     Rgb(u8, u8, u8),
+    /// red, green, blue and an alpha between 0 and 1
+    Rgba(u8, u8, u8, f32),
+    /// `0xRRGGBB`
     Hex(u32),
+    /// `0xRRGGBBAA`
+    HexAlpha(u32),
     Hsl(u16, u8, u8),
+    /// hue, saturation, lightness and an alpha between 0 and 1
+    Hsla(u16, u8, u8, f32),
 }
 
 impl Display for Color {
@@ -13,14 +34,407 @@ impl Display for Color {
         match self {
             Color::Named(name) => write!(fmt, "{}", name),
             Color::Rgb(r, g, b) => write!(fmt, "rgb({},{},{})", r, g, b),
+            Color::Rgba(r, g, b, a) => {
+                write!(fmt, "rgba({},{},{},{})", r, g, b, clamp_alpha(*a))
+            }
             Color::Hex(hex) => write!(fmt, "#{:06X}", hex),
+            Color::HexAlpha(hex) => write!(fmt, "#{:08X}", hex),
             Color::Hsl(h, s, l) => {
                 write!(fmt, "hsl({},{}%,{}%)", h % 360, s.min(&100), l.min(&100))
             }
+            Color::Hsla(h, s, l, a) => write!(
+                fmt,
+                "hsla({},{}%,{}%,{})",
+                h % 360,
+                s.min(&100),
+                l.min(&100),
+                clamp_alpha(*a)
+            ),
+        }
+    }
+}
+
+impl Color {
+    /// opacity of this color, between 0 and 1
+    pub fn alpha(&self) -> f32 {
+        match *self {
+            Color::Rgba(_, _, _, a) | Color::Hsla(_, _, _, a) => clamp_alpha(a),
+            Color::HexAlpha(hex) => (hex & 0xFF) as f32 / 255.0,
+            _ => 1.0,
+        }
+    }
+
+    /// this color as [`Color::Rgb`], or [`Color::Rgba`] if it isn't opaque; `None` for unknown
+    /// named colors
+    pub fn to_rgb(&self) -> Option<Color> {
+        let (r, g, b, a) = self.rgba_components()?;
+        Some(if a < 1.0 {
+            Color::Rgba(r, g, b, a)
+        } else {
+            Color::Rgb(r, g, b)
+        })
+    }
+
+    /// this color as [`Color::Hex`], or [`Color::HexAlpha`] if it isn't opaque; `None` for
+    /// unknown named colors
+    pub fn to_hex(&self) -> Option<Color> {
+        let (r, g, b, a) = self.rgba_components()?;
+        let hex = ((r as u32) << 16) | ((g as u32) << 8) | b as u32;
+        Some(if a < 1.0 {
+            Color::HexAlpha((hex << 8) | (a * 255.0).round() as u32)
+        } else {
+            Color::Hex(hex)
+        })
+    }
+
+    /// this color as [`Color::Hsl`], or [`Color::Hsla`] if it isn't opaque; `None` for unknown
+    /// named colors
+    pub fn to_hsl(&self) -> Option<Color> {
+        let (h, s, l) = match *self {
+            Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) => (h % 360, s.min(100), l.min(100)),
+            _ => {
+                let (r, g, b, _) = self.rgba_components()?;
+                rgb_to_hsl(r, g, b)
+            }
+        };
+        let a = self.alpha();
+        Some(if a < 1.0 {
+            Color::Hsla(h, s, l, a)
+        } else {
+            Color::Hsl(h, s, l)
+        })
+    }
+
+    /// this color with its lightness increased by `amount`, between 0 and 1, see
+    /// [`saturate`] for the representation of the result; unknown named colors are returned as
+    /// is
+    ///
+    /// [`saturate`]: Color::saturate
+    pub fn lighten(&self, amount: f32) -> Color {
+        self.adjust_hsl(0.0, amount)
+    }
+
+    /// this color with its lightness decreased by `amount`, between 0 and 1; unknown named
+    /// colors are returned as is
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// this color with its saturation increased by `amount`, between 0 and 1; unknown named
+    /// colors are returned as is
+    ///
+    /// HSL colors stay in HSL and hex colors in hex, other colors are returned in RGB. Only HSL
+    /// colors are rounded to whole percentages, so a zero `amount` returns the same color.
+    pub fn saturate(&self, amount: f32) -> Color {
+        self.adjust_hsl(amount, 0.0)
+    }
+
+    /// this color with its saturation decreased by `amount`, between 0 and 1; unknown named
+    /// colors are returned as is
+    pub fn desaturate(&self, amount: f32) -> Color {
+        self.saturate(-amount)
+    }
+
+    /// this color with `saturation` and `lightness` added to its own in HSL, as fractions
+    /// between -1 and 1
+    fn adjust_hsl(&self, saturation: f32, lightness: f32) -> Color {
+        if let Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) = *self {
+            let (h, s, l) = (h % 360, s.min(100), l.min(100));
+            let add = |value: u8, delta: f32| {
+                (value as f32 + delta * 100.0).round().clamp(0.0, 100.0) as u8
+            };
+            let (s, l) = (add(s, saturation), add(l, lightness));
+            let a = self.alpha();
+            return if a < 1.0 {
+                Color::Hsla(h, s, l, a)
+            } else {
+                Color::Hsl(h, s, l)
+            };
+        }
+        let Some((r, g, b, a)) = self.rgba_components() else {
+            return *self;
+        };
+        // adjusted without rounding to whole percentages, which would shift the channels
+        let (h, s, l) = rgb_to_exact_hsl(r, g, b);
+        let add = |value: f64, delta: f32| (value + delta as f64).clamp(0.0, 1.0);
+        let (r, g, b) = exact_hsl_to_rgb(h, add(s, saturation), add(l, lightness));
+        let rgb = if a < 1.0 {
+            Color::Rgba(r, g, b, a)
+        } else {
+            Color::Rgb(r, g, b)
+        };
+        match self {
+            Color::Hex(_) | Color::HexAlpha(_) => rgb.to_hex().unwrap_or(rgb),
+            _ => rgb,
+        }
+    }
+
+    /// mix of this color and `other` in RGB, from this color at a `ratio` of 0 to `other` at
+    /// a ratio of 1; an unknown named color can't be mixed, so the closest of both colors is
+    /// returned instead
+    pub fn mix(&self, other: &Color, ratio: f32) -> Color {
+        let ratio = ratio.clamp(0.0, 1.0);
+        match (self.rgba_components(), other.rgba_components()) {
+            (Some((r0, g0, b0, a0)), Some((r1, g1, b1, a1))) => {
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+                let alpha = a0 + (a1 - a0) * ratio;
+                if alpha < 1.0 {
+                    Color::Rgba(mix(r0, r1), mix(g0, g1), mix(b0, b1), alpha)
+                } else {
+                    Color::Rgb(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+                }
+            }
+            _ if ratio < 0.5 => *self,
+            _ => *other,
+        }
+    }
+
+    /// relative luminance of this color as defined by WCAG, from 0 for black to 1 for white,
+    /// ignoring its alpha; `None` for unknown named colors
+    pub fn relative_luminance(&self) -> Option<f64> {
+        let (r, g, b, _) = self.rgba_components()?;
+        let linear = |channel: u8| {
+            let channel = channel as f64 / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+    }
+
+    /// contrast ratio between this color and `other` as defined by WCAG, from 1 for identical
+    /// luminances to 21 for black on white; `None` for unknown named colors
+    pub fn contrast_ratio(&self, other: &Color) -> Option<f64> {
+        let (a, b) = (self.relative_luminance()?, other.relative_luminance()?);
+        Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+
+    /// black or white, whichever is the most readable on this color, e.g. for labels drawn on
+    /// filled polygons; black for unknown named colors
+    pub fn readable_text_color(&self) -> Color {
+        match (
+            self.contrast_ratio(&Color::BLACK),
+            self.contrast_ratio(&Color::WHITE),
+        ) {
+            (Some(black), Some(white)) if white > black => Color::WHITE,
+            _ => Color::BLACK,
         }
     }
+
+    /// red, green, blue and alpha components of this color, `None` for unknown named colors
+    pub(crate) fn rgba_components(&self) -> Option<(u8, u8, u8, f32)> {
+        let (r, g, b) = match *self {
+            Color::Named(name) => {
+                let (_, hex) = named_color(&name.to_ascii_lowercase())?;
+                hex_to_rgb(hex)
+            }
+            Color::Rgb(r, g, b) | Color::Rgba(r, g, b, _) => (r, g, b),
+            Color::Hex(hex) => hex_to_rgb(hex),
+            Color::HexAlpha(hex) => hex_to_rgb(hex >> 8),
+            Color::Hsl(h, s, l) | Color::Hsla(h, s, l, _) => hsl_to_rgb(h, s, l),
+        };
+        Some((r, g, b, self.alpha()))
+    }
+}
+
+/// `alpha` clamped between 0 and 1, opaque if it's NaN
+fn clamp_alpha(alpha: f32) -> f32 {
+    if alpha.is_nan() {
+        1.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    }
+}
+
+fn hex_to_rgb(hex: u32) -> (u8, u8, u8) {
+    ((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+fn hsl_to_rgb(h: u16, s: u8, l: u8) -> (u8, u8, u8) {
+    exact_hsl_to_rgb(
+        (h % 360) as f64,
+        s.min(100) as f64 / 100.0,
+        l.min(100) as f64 / 100.0,
+    )
+}
+
+/// channels of the color with the hue `h` in degrees between 0 and 360 and the saturation `s`
+/// and lightness `l` between 0 and 1
+fn exact_hsl_to_rgb(h: f64, s: f64, l: f64) -> (u8, u8, u8) {
+    let h = h / 60.0;
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (u16, u8, u8) {
+    let (h, s, l) = rgb_to_exact_hsl(r, g, b);
+    (
+        h.round() as u16 % 360,
+        (s * 100.0).round() as u8,
+        (l * 100.0).round() as u8,
+    )
+}
+
+/// hue in degrees between 0 and 360, saturation and lightness between 0 and 1 of the color
+/// with the given channels
+fn rgb_to_exact_hsl(r: u8, g: u8, b: u8) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+    let l = (max + min) / 2.0;
+    if chroma == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    (h * 60.0, s, l)
 }
 
+/// Error returned when parsing a string that isn't a CSS color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl Display for ParseColorError {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "invalid CSS color: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// parse a CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`, `hsl()`,
+    /// `hsla()`, `transparent` or a named color
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_color(&s.trim().to_ascii_lowercase()).ok_or_else(|| ParseColorError(s.to_string()))
+    }
+}
+
+/// parse a CSS color in lower case
+fn parse_color(input: &str) -> Option<Color> {
+    if let Some(hex) = input.strip_prefix('#') {
+        parse_hex(hex)
+    } else if let Some(arguments) = function_arguments(input, "rgb") {
+        match arguments[..] {
+            [r, g, b] => Some(Color::Rgb(channel(r)?, channel(g)?, channel(b)?)),
+            [r, g, b, a] => Some(Color::Rgba(
+                channel(r)?,
+                channel(g)?,
+                channel(b)?,
+                alpha(a)?,
+            )),
+            _ => None,
+        }
+    } else if let Some(arguments) = function_arguments(input, "hsl") {
+        match arguments[..] {
+            [h, s, l] => Some(Color::Hsl(hue(h)?, percentage(s)?, percentage(l)?)),
+            [h, s, l, a] => Some(Color::Hsla(
+                hue(h)?,
+                percentage(s)?,
+                percentage(l)?,
+                alpha(a)?,
+            )),
+            _ => None,
+        }
+    } else if input == "transparent" {
+        Some(Color::Rgba(0, 0, 0, 0.0))
+    } else {
+        named_color(input).map(|(name, _)| Color::Named(name))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    // doubles each digit of short forms, turning `0xabc` into `0xaabbcc`
+    let expand = |value: u32, digits: u32| {
+        (0..digits).rev().fold(0, |expanded, digit| {
+            (expanded << 8) | (((value >> (digit * 4)) & 0xF) * 0x11)
+        })
+    };
+    match hex.len() {
+        3 => Some(Color::Hex(expand(value, 3))),
+        4 => Some(Color::HexAlpha(expand(value, 4))),
+        6 => Some(Color::Hex(value)),
+        8 => Some(Color::HexAlpha(value)),
+        _ => None,
+    }
+}
+
+/// arguments of the CSS function `name`, or of its variant with an alpha, e.g. `rgba`
+fn function_arguments<'a>(input: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let input = input.strip_prefix(name)?;
+    let input = input.strip_prefix('a').unwrap_or(input);
+    let arguments = input.strip_prefix('(')?.strip_suffix(')')?;
+    Some(
+        arguments
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|argument| !argument.is_empty())
+            .collect(),
+    )
+}
+
+/// finite number, or percentage of `scale`
+fn number(argument: &str, scale: f32) -> Option<f32> {
+    match argument.strip_suffix('%') {
+        Some(percentage) => Some(finite(percentage)? * scale / 100.0),
+        None => finite(argument),
+    }
+}
+
+/// finite number, rejecting the `inf` and `nan` Rust parses but CSS doesn't
+fn finite(argument: &str) -> Option<f32> {
+    argument
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+}
+
+fn channel(argument: &str) -> Option<u8> {
+    Some(number(argument, 255.0)?.round().clamp(0.0, 255.0) as u8)
+}
+
+fn alpha(argument: &str) -> Option<f32> {
+    Some(number(argument, 1.0)?.clamp(0.0, 1.0))
+}
+
+fn hue(argument: &str) -> Option<u16> {
+    let degrees = finite(argument.strip_suffix("deg").unwrap_or(argument))?;
+    // beyond 2^24 `f32` skips whole degrees, so the angle can't be reduced to a turn
+    if degrees.abs() > (1 << f32::MANTISSA_DIGITS) as f32 {
+        return None;
+    }
+    Some(degrees.round().rem_euclid(360.0) as u16)
+}
+
+fn percentage(argument: &str) -> Option<u8> {
+    let value = finite(argument.strip_suffix('%').unwrap_or(argument))?;
+    Some(value.round().clamp(0.0, 100.0) as u8)
+}
 
 #[cfg(test)]
 mod tests
@@ -51,4 +465,143 @@ mod tests
     {
         assert_eq!(format!("{}", Color::Hsl(0, 100, 50)), "hsl(0,100%,50%)");
     }
+
+    #[test]
+    fn test_alpha() {
+        assert_eq!(
+            format!("{}", Color::Rgba(255, 0, 0, 0.5)),
+            "rgba(255,0,0,0.5)"
+        );
+        assert_eq!(format!("{}", Color::HexAlpha(0xFF000080)), "#FF000080");
+        assert_eq!(
+            format!("{}", Color::Hsla(0, 100, 50, 0.25)),
+            "hsla(0,100%,50%,0.25)"
+        );
+        assert_eq!(
+            format!("{}", Color::Rgba(255, 0, 0, f32::NAN)),
+            "rgba(255,0,0,1)"
+        );
+        assert_eq!(Color::Hsla(0, 100, 50, f32::NAN).alpha(), 1.0);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("#abc".parse(), Ok(Color::Hex(0xAABBCC)));
+        assert_eq!("#abcd".parse(), Ok(Color::HexAlpha(0xAABBCCDD)));
+        assert_eq!(" #A0B1C2 ".parse(), Ok(Color::Hex(0xA0B1C2)));
+        assert_eq!("#a0b1c2ff".parse(), Ok(Color::HexAlpha(0xA0B1C2FF)));
+        assert_eq!("rgb(255,0,10)".parse(), Ok(Color::Rgb(255, 0, 10)));
+        assert_eq!(
+            "rgb(100% 0% 0% / 50%)".parse(),
+            Ok(Color::Rgba(255, 0, 0, 0.5))
+        );
+        assert_eq!("hsl(120, 100%, 25%)".parse(), Ok(Color::Hsl(120, 100, 25)));
+        assert_eq!(
+            "hsla(-90deg,50%,50%,0.3)".parse(),
+            Ok(Color::Hsla(270, 50, 50, 0.3))
+        );
+        assert_eq!("RebeccaPurple".parse(), Ok(Color::Named("rebeccapurple")));
+        assert_eq!("transparent".parse(), Ok(Color::Rgba(0, 0, 0, 0.0)));
+        for invalid in [
+            "gren",
+            "#abcde",
+            "rgb(1,2)",
+            "hsl(a,b,c)",
+            "#ggg",
+            "",
+            "rgb(nan,0,0)",
+            "rgb(inf,0,0)",
+            "hsl(1e20,0%,0%)",
+            "hsl(0,infinity%,0%)",
+            "rgba(0,0,0,nan)",
+        ] {
+            assert_eq!(
+                invalid.parse::<Color>(),
+                Err(ParseColorError(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_lighten() {
+        assert_eq!(
+            Color::Hsl(210, 50, 50).lighten(0.2),
+            Color::Hsl(210, 50, 70)
+        );
+        assert_eq!(Color::Rgb(64, 128, 191).darken(0.6), Color::Rgb(0, 0, 0));
+        assert_eq!(Color::Hex(0x808080).lighten(0.5), Color::Hex(0xFFFFFF));
+        assert_eq!(
+            Color::Hsla(0, 50, 50, 0.5).desaturate(0.25),
+            Color::Hsla(0, 25, 50, 0.5)
+        );
+        assert_eq!(
+            Color::Named("unknown").saturate(0.1),
+            Color::Named("unknown")
+        );
+    }
+
+    #[test]
+    fn test_adjust_round_trip() {
+        for color in [
+            Color::Hex(0x2171B5),
+            Color::HexAlpha(0x2171B580),
+            Color::Rgb(1, 2, 3),
+            Color::Rgba(250, 128, 7, 0.5),
+            Color::Hsl(208, 69, 42),
+        ] {
+            assert_eq!(color.lighten(0.0), color);
+            assert_eq!(color.desaturate(0.0), color);
+        }
+        assert_eq!(Color::TEAL.darken(0.0), Color::Rgb(0, 128, 128));
+    }
+
+    #[test]
+    fn test_mix() {
+        let (black, white) = (Color::Rgb(0, 0, 0), Color::Hex(0xFFFFFF));
+        assert_eq!(black.mix(&white, 0.25), Color::Rgb(64, 64, 64));
+        assert_eq!(
+            black.mix(&Color::Rgba(0, 0, 0, 0.0), 0.5),
+            Color::Rgba(0, 0, 0, 0.5)
+        );
+        assert_eq!(black.mix(&Color::Named("unknown"), 0.4), black);
+    }
+
+    #[test]
+    fn test_contrast() {
+        assert_eq!(Color::BLACK.relative_luminance(), Some(0.0));
+        assert_eq!(Color::WHITE.relative_luminance(), Some(1.0));
+        assert_eq!(Color::BLACK.contrast_ratio(&Color::WHITE), Some(21.0));
+        assert_eq!(Color::NAVY.readable_text_color(), Color::WHITE);
+        assert_eq!(Color::Hex(0xFDE725).readable_text_color(), Color::BLACK);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            Color::Hex(0x2171B5).to_rgb(),
+            Some(Color::Rgb(0x21, 0x71, 0xB5))
+        );
+        assert_eq!(
+            Color::Hsl(120, 100, 25).to_rgb(),
+            Some(Color::Rgb(0, 128, 0))
+        );
+        assert_eq!(
+            Color::Hsl(210, 50, 50).to_rgb(),
+            Some(Color::Rgb(64, 128, 191))
+        );
+        assert_eq!(Color::Named("teal").to_hex(), Some(Color::Hex(0x008080)));
+        assert_eq!(Color::Named("unknown").to_rgb(), None);
+        assert_eq!(
+            Color::Rgba(255, 0, 0, 0.5).to_hex(),
+            Some(Color::HexAlpha(0xFF000080))
+        );
+        assert_eq!(
+            Color::Rgb(64, 128, 191).to_hsl(),
+            Some(Color::Hsl(210, 50, 50))
+        );
+        assert_eq!(
+            Color::HexAlpha(0x00800080).to_hsl(),
+            Some(Color::Hsla(120, 100, 25, 128.0 / 255.0))
+        );
+    }
 }
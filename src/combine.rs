@@ -1,19 +1,72 @@
-use crate::{Svg, ToSvg};
+use crate::{Style, Svg, ToSvg};
 
 /// This trait let's you combine multiple things that can be converted to a SVG into one big
 /// compound SVG
 pub trait CombineToSVG {
-    fn combine_to_svg(&self) -> Option<Svg>;
+    fn combine_to_svg(&self) -> Option<Svg<'_>>;
 }
 
 impl<S: ToSvg> CombineToSVG for &[S] {
-    fn combine_to_svg(&self) -> Option<Svg> {
+    fn combine_to_svg(&self) -> Option<Svg<'_>> {
         self.iter().map(|s| s.to_svg()).reduce(|a, b| a.and(b))
     }
 }
 
 impl<S: ToSvg> CombineToSVG for Vec<S> {
-    fn combine_to_svg(&self) -> Option<Svg> {
+    fn combine_to_svg(&self) -> Option<Svg<'_>> {
         self.iter().map(|s| s.to_svg()).reduce(|a, b| a.and(b))
     }
 }
+
+/// Converts a collection to a SVG styling each of its elements individually, e.g. from the data
+/// of features:
+///
+/// ```
+/// # use geo::Point;
+/// # use geo_svg::{Color, Style, ToSvgWith};
+/// let wells = vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0)];
+/// let svg = wells.to_svg_with(|well, _index| Style {
+///     fill: Some(if well.x() > 5.0 { Color::RED } else { Color::BLUE }.into()),
+///     ..Style::default()
+/// });
+/// assert_eq!(
+///     svg.svg_str(),
+///     r#"<circle cx="0.0" cy="0.0" r="1" fill="blue"/><circle cx="10.0" cy="0.0" r="1" fill="red"/>"#
+/// );
+/// ```
+///
+/// The styles cascade like the ones of documents combined with [`and`], so the builders of the
+/// returned document fill in the properties they don't set.
+///
+/// [`and`]: crate::SvgDocument::and
+pub trait ToSvgWith<S> {
+    /// style the element at each index with the style returned by `style`
+    fn to_svg_with(&self, style: impl FnMut(&S, usize) -> Style) -> Svg<'_>;
+}
+
+impl<S: ToSvg> ToSvgWith<S> for &[S] {
+    fn to_svg_with(&self, style: impl FnMut(&S, usize) -> Style) -> Svg<'_> {
+        styled_siblings(self, style)
+    }
+}
+
+impl<S: ToSvg> ToSvgWith<S> for Vec<S> {
+    fn to_svg_with(&self, style: impl FnMut(&S, usize) -> Style) -> Svg<'_> {
+        styled_siblings(self, style)
+    }
+}
+
+/// document with one sibling per element of `elements`, styled by `style`; unlike chaining
+/// [`and`] this keeps the tree flat however many elements there are
+///
+/// [`and`]: crate::SvgDocument::and
+fn styled_siblings<S: ToSvg>(elements: &[S], mut style: impl FnMut(&S, usize) -> Style) -> Svg<'_> {
+    Svg {
+        siblings: elements
+            .iter()
+            .enumerate()
+            .map(|(index, element)| element.to_svg().with_style(&style(element, index)))
+            .collect(),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{Gradient, Marker, Pattern, Precision, Symbol};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter, Result, Write},
+};
+
+/// Element written once in the `<defs>` of a document and referenced by its id from the
+/// elements of the document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Definition {
+    Gradient(Gradient),
+    Pattern(Pattern),
+    Marker(Marker),
+    Symbol(Symbol),
+}
+
+impl Definition {
+    pub fn id(&self) -> Cow<'_, str> {
+        match self {
+            Definition::Gradient(gradient) => Cow::Borrowed(&gradient.id),
+            Definition::Pattern(pattern) => Cow::Borrowed(&pattern.id),
+            Definition::Marker(marker) => Cow::Owned(marker.id()),
+            Definition::Symbol(symbol) => Cow::Owned(symbol.id()),
+        }
+    }
+
+    /// this definition with the given `id`, or `None` if its id is derived from its content
+    /// rather than chosen by the user
+    pub(crate) fn with_id(&self, id: String) -> Option<Definition> {
+        match self {
+            Definition::Gradient(gradient) => Some(Definition::Gradient(Gradient {
+                id,
+                ..gradient.clone()
+            })),
+            Definition::Pattern(pattern) => Some(Definition::Pattern(Pattern {
+                id,
+                ..pattern.clone()
+            })),
+            Definition::Marker(_) => None,
+            Definition::Symbol(symbol) => symbol.with_id(id).map(Definition::Symbol),
+        }
+    }
+
+    /// write this definition, with the numbers of gradients and patterns rounded to
+    /// `precision`; symbols carry the precision of their style
+    pub(crate) fn write(&self, writer: &mut dyn Write, precision: Option<Precision>) -> Result {
+        match self {
+            Definition::Gradient(gradient) => gradient.write(writer, precision),
+            Definition::Pattern(pattern) => pattern.write(writer, precision),
+            Definition::Marker(marker) => write!(writer, "{}", marker),
+            Definition::Symbol(symbol) => write!(writer, "{}", symbol),
+        }
+    }
+}
+
+impl Display for Definition {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        self.write(fmt, None)
+    }
+}
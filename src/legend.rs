@@ -0,0 +1,390 @@
+use crate::{
+    precision::DisplayNumber, svg::Escaped, symbol::SymbolPath, ColorScale, PointSymbol, Precision,
+    RenderContext, Style, ToSvgStr, ViewBox,
+};
+use geo::Coord;
+use std::fmt::{Result, Write};
+
+/// Shape drawn next to the label of a [`Legend`] entry, matching the kind of geometries it
+/// describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Swatch {
+    /// filled box, for polygons
+    Polygon,
+    /// horizontal line, for line strings
+    Line,
+    /// point symbol of the style of the entry, for points
+    Point,
+}
+
+/// Corner of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendEntry {
+    pub swatch: Swatch,
+    pub style: Style,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Placement {
+    /// top left corner of the legend
+    At(Coord<f64>),
+    /// corner of the viewbox the legend is placed in
+    Corner(ViewBox, Corner),
+}
+
+/// Legend listing styles with their labels, one entry per row.
+///
+/// Sizes are in document units, like the font size of [`Text`]. The legend is included in the
+/// viewbox of the documents it's combined into, so place it in a corner of the viewbox of the
+/// rest of the document to keep the same extent:
+///
+/// ```
+/// # use geo::Point;
+/// # use geo_svg::{Color, Corner, Legend, Style, Swatch, ToSvg};
+/// let wells = Point::new(0.0, 0.0);
+/// let map = wells.to_svg().with_fill_color(Color::Named("blue"));
+/// let legend = Legend::new()
+///     .with_entry(
+///         Swatch::Point,
+///         &Style {
+///             fill: Some(Color::Named("blue").into()),
+///             ..Style::default()
+///         },
+///         "wells",
+///     )
+///     .with_size(0.5)
+///     .in_corner(&map.viewbox(), Corner::BottomRight);
+/// let svg = map.and(legend.to_svg());
+/// ```
+///
+/// [`Text`]: crate::Text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Legend {
+    pub entries: Vec<LegendEntry>,
+    /// size of the swatches and font size of the labels
+    pub size: f64,
+    placement: Placement,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            size: 10.0,
+            placement: Placement::At(Coord::zero()),
+        }
+    }
+}
+
+impl Legend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// legend of the colors of `scale`, one entry per class of classed scales and five evenly
+    /// spaced values for linear scales
+    pub fn from_scale(scale: &ColorScale, swatch: Swatch) -> Self {
+        let number = |value: f64| DisplayNumber(value, Some(Precision::Significant(3)));
+        let entries: Vec<(f64, String)> = match scale {
+            ColorScale::Linear { min, max, .. } => (0..5)
+                .map(|i| {
+                    let value = min + (max - min) * i as f64 / 4.0;
+                    (value, number(value).to_string())
+                })
+                .collect(),
+            ColorScale::Classed { classification, .. } => classification
+                .bounds
+                .windows(2)
+                .map(|bounds| {
+                    let label = format!("{} – {}", number(bounds[0]), number(bounds[1]));
+                    ((bounds[0] + bounds[1]) / 2.0, label)
+                })
+                .collect(),
+        };
+        entries
+            .into_iter()
+            .fold(Legend::new(), |legend, (value, label)| {
+                legend.with_entry(swatch, &scale.fill(value), label)
+            })
+    }
+
+    pub fn with_entry(mut self, swatch: Swatch, style: &Style, label: impl Into<String>) -> Self {
+        self.entries.push(LegendEntry {
+            swatch,
+            style: style.clone(),
+            label: label.into(),
+        });
+        self
+    }
+
+    /// set the size of the swatches and the font size of the labels
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// place the top left corner of the legend at `position`
+    pub fn at(mut self, position: Coord<f64>) -> Self {
+        self.placement = Placement::At(position);
+        self
+    }
+
+    /// place the legend inside `corner` of `viewbox`, as seen in the rendered document
+    pub fn in_corner(mut self, viewbox: &ViewBox, corner: Corner) -> Self {
+        self.placement = Placement::Corner(*viewbox, corner);
+        self
+    }
+
+    fn row_height(&self) -> f64 {
+        self.size * 1.5
+    }
+
+    /// width and height of the legend, estimating the width of the labels from their length
+    fn extent(&self) -> (f64, f64) {
+        let label_length = self
+            .entries
+            .iter()
+            .map(|entry| entry.label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = self.row_height() + 0.6 * self.size * label_length as f64;
+        let height = (self.row_height() * self.entries.len() as f64 - self.size * 0.5).max(0.0);
+        (width, height)
+    }
+
+    /// top left corner of the legend as seen in the rendered document, where the y axis points
+    /// down even if the document flips it
+    fn origin(&self, y_up: bool) -> Coord<f64> {
+        match &self.placement {
+            Placement::At(Coord { x, y }) => Coord {
+                x: *x,
+                y: if y_up { -y } else { *y },
+            },
+            Placement::Corner(viewbox, corner) => {
+                let viewbox = if y_up { viewbox.flip_y() } else { *viewbox };
+                let (width, height) = self.extent();
+                let margin = self.size * 0.5;
+                let x = match corner {
+                    Corner::TopLeft | Corner::BottomLeft => viewbox.min_x() + margin,
+                    Corner::TopRight | Corner::BottomRight => viewbox.max_x() - margin - width,
+                };
+                let y = match corner {
+                    Corner::TopLeft | Corner::TopRight => viewbox.min_y() + margin,
+                    Corner::BottomLeft | Corner::BottomRight => viewbox.max_y() - margin - height,
+                };
+                Coord { x, y }
+            }
+        }
+    }
+}
+
+impl ToSvgStr for Legend {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        if render.y_up {
+            // draw in the unflipped coordinates of the rendered document
+            writer.write_str(r#"<g transform="scale(1,-1)">"#)?;
+        }
+        let origin = self.origin(render.y_up);
+        let size = self.size;
+        let number = |value: f64| DisplayNumber(value, style.precision);
+        for (i, entry) in self.entries.iter().enumerate() {
+            let (x, y) = (origin.x, origin.y + self.row_height() * i as f64);
+            let entry_style = entry.style.inherit(style);
+            match entry.swatch {
+                Swatch::Polygon => write!(
+                    writer,
+                    r#"<rect x="{x}" y="{y}" width="{size}" height="{size}"{entry_style}/>"#,
+                    x = number(x),
+                    y = number(y),
+                    size = number(size),
+                )?,
+                Swatch::Line => write!(
+                    writer,
+                    r#"<path d="M {x0} {y} L {x1} {y}"{entry_style}/>"#,
+                    x0 = number(x),
+                    x1 = number(x + size),
+                    y = number(y + size / 2.0),
+                )?,
+                Swatch::Point => {
+                    // a third of the swatch, rounded like the auto sizes of documents
+                    let default_radius = Precision::Significant(2)
+                        .round(size / 3.0)
+                        .map_or(size / 3.0, |(radius, _)| radius);
+                    let radius = entry.style.radius.map_or(default_radius, |_| {
+                        (entry_style.point_radius(render) as f64).min(size / 2.0)
+                    });
+                    let center = (x + size / 2.0, y + size / 2.0);
+                    match &entry_style.point_symbol {
+                        Some(PointSymbol::Custom { path, .. }) => write!(
+                            writer,
+                            r#"<path d="{path}" transform="translate({cx},{cy})"{entry_style}/>"#,
+                            path = Escaped(path),
+                            cx = number(center.0),
+                            cy = number(center.1),
+                        )?,
+                        Some(symbol) if *symbol != PointSymbol::Circle => {
+                            let path = SymbolPath {
+                                symbol,
+                                center,
+                                radius,
+                                flip_y: false,
+                                precision: style.precision,
+                            };
+                            write!(writer, r#"<path d="{path}"{entry_style}/>"#)?
+                        }
+                        _ => write!(
+                            writer,
+                            r#"<circle cx="{cx}" cy="{cy}" r="{radius}"{entry_style}/>"#,
+                            cx = number(center.0),
+                            cy = number(center.1),
+                            radius = number(radius),
+                        )?,
+                    }
+                }
+            }
+            write!(
+                writer,
+                r#"<text font-size="{size}" x="{x}" y="{y}">{label}</text>"#,
+                size = number(size),
+                x = number(x + self.row_height()),
+                y = number(y + size * 0.85),
+                label = Escaped(&entry.label),
+            )?;
+        }
+        if render.y_up {
+            writer.write_str("</g>")?;
+        }
+        Ok(())
+    }
+
+    fn viewbox(&self, _style: &Style, render: &RenderContext) -> ViewBox {
+        if self.entries.is_empty() {
+            return ViewBox::default();
+        }
+        let origin = self.origin(render.y_up);
+        let (width, height) = self.extent();
+        let viewbox = ViewBox::new(origin.x, origin.y, origin.x + width, origin.y + height);
+        if render.y_up {
+            viewbox.flip_y()
+        } else {
+            viewbox
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{palette, Classification, Color, ColorRamp, ToSvg};
+
+    #[test]
+    fn test_entries() {
+        let legend = Legend::new()
+            .with_entry(
+                Swatch::Polygon,
+                &Style {
+                    fill: Some(Color::Named("green").into()),
+                    ..Style::default()
+                },
+                "parks",
+            )
+            .with_entry(
+                Swatch::Line,
+                &Style {
+                    stroke_color: Some(Color::Named("black").into()),
+                    ..Style::default()
+                },
+                "roads & paths",
+            )
+            .with_entry(Swatch::Point, &Style::default(), "wells")
+            .at(Coord { x: 0.0, y: 0.0 });
+        assert_eq!(
+            legend.to_svg().svg_str(),
+            r#"<rect x="0" y="0" width="10" height="10" fill="green"/><text font-size="10" x="15" y="8.5">parks</text><path d="M 0 20 L 10 20" stroke="black"/><text font-size="10" x="15" y="23.5">roads &amp; paths</text><circle cx="5" cy="35" r="3.3"/><text font-size="10" x="15" y="38.5">wells</text>"#
+        );
+        assert_eq!(
+            legend.to_svg().viewbox(),
+            ViewBox::new(0.0, 0.0, 93.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn test_corner() {
+        let viewbox = ViewBox::new(0.0, 0.0, 100.0, 50.0);
+        let legend = Legend::new()
+            .with_entry(Swatch::Polygon, &Style::default(), "a")
+            .with_size(2.0)
+            .in_corner(&viewbox, Corner::BottomRight);
+        let style = Style::default();
+        let render = RenderContext::default();
+        assert_eq!(
+            legend.viewbox(&style, &render),
+            ViewBox::new(94.8, 47.0, 99.0, 49.0)
+        );
+        let render = RenderContext {
+            y_up: true,
+            ..RenderContext::default()
+        };
+        assert_eq!(
+            legend.viewbox(&style, &render),
+            ViewBox::new(94.8, 1.0, 99.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_from_scale() {
+        let classification = Classification::equal_interval(&[0.0, 100.0], 2);
+        let scale = ColorScale::classed(classification, &ColorRamp::new(palette::BLUES));
+        let legend = Legend::from_scale(&scale, Swatch::Polygon);
+        let labels: Vec<_> = legend.entries.iter().map(|entry| &entry.label).collect();
+        assert_eq!(labels, ["0 – 50", "50 – 100"]);
+        assert_eq!(
+            legend.entries[1].style.fill,
+            Some(Color::Hex(0x08306B).into())
+        );
+    }
+
+    #[test]
+    fn test_point_symbol() {
+        let legend = Legend::new().with_entry(
+            Swatch::Point,
+            &Style {
+                point_symbol: Some(PointSymbol::Diamond),
+                radius: Some(2.0),
+                ..Style::default()
+            },
+            "stations",
+        );
+        assert_eq!(
+            legend.to_svg().svg_str(),
+            r#"<path d="M 5 3 L 7 5 L 5 7 L 3 5 Z"/><text font-size="10" x="15" y="8.5">stations</text>"#
+        );
+    }
+
+    #[test]
+    fn test_custom_symbol() {
+        let legend = Legend::new()
+            .with_entry(
+                Swatch::Point,
+                &Style {
+                    point_symbol: Some(PointSymbol::custom("tree", "M 0 -1 L 1 1 L -1 1 Z")),
+                    ..Style::default()
+                },
+                "trees",
+            )
+            .at(Coord { x: 40.0, y: 40.0 });
+        assert_eq!(
+            legend.to_svg().svg_str(),
+            r#"<path d="M 0 -1 L 1 1 L -1 1 Z" transform="translate(45,45)"/><text font-size="10" x="55" y="48.5">trees</text>"#
+        );
+    }
+}
@@ -41,7 +41,7 @@
 //! ## Result
 //!
 //! ```xml
-//! <svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><circle cx="10" cy="28.1" r="2" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"/><path d="M 114.19 22.26 L 15.93 -15.76" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)" stroke-width="2.5"/></svg>
+//! <svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="7 -18.26 109.69 49.36"><circle cx="10.0" cy="28.1" r="2" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)"/><path d="M 114.19 22.26 L 15.93 -15.76" fill="red" fill-opacity="0.7" stroke="rgb(200,0,100)" stroke-width="2.5"/></svg>
 //! ```
 //!
 //! [`ToSvg`]: svg/trait.ToSvg.html
@@ -49,20 +49,46 @@
 
 mod color;
 mod combine;
+mod defs;
+mod legend;
+mod marker;
+mod named_colors;
+mod paint;
+pub mod palette;
+mod path;
+mod pattern;
+mod polylabel;
+mod precision;
+mod ramp;
+mod render;
 mod style;
+mod stylesheet;
 mod svg;
 mod svg_impl;
+mod symbol;
 mod text;
 mod to_svg;
 mod to_svg_str;
 mod viewbox;
+mod viewport;
 
 pub use color::*;
 pub use combine::*;
+pub use defs::Definition;
+pub use legend::*;
+pub use marker::*;
+pub use paint::*;
+pub use pattern::*;
+pub use polylabel::VisualCenter;
+pub use precision::Precision;
+pub use ramp::*;
+pub use render::RenderContext;
 pub use style::*;
-pub use svg::Svg;
-pub use svg_impl::*;
+pub use stylesheet::Stylesheet;
+pub use svg::{OwnedSvg, Svg, SvgDocument};
+pub use symbol::{PointSymbol, Symbol};
 pub use text::*;
 pub use to_svg::*;
 pub use to_svg_str::*;
 pub use viewbox::ViewBox;
+pub use viewport::*;
@@ -0,0 +1,185 @@
+use crate::Color;
+use std::fmt::{Display, Formatter, Result};
+
+/// Shape of a [`Marker`], pointing along the line at its vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerShape {
+    /// arrowhead with its tip on the vertex, pointing backwards at the start of lines
+    Arrow,
+    Circle,
+    Square,
+    /// bar across the line
+    Bar,
+}
+
+impl Display for MarkerShape {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            MarkerShape::Arrow => write!(fmt, "arrow"),
+            MarkerShape::Circle => write!(fmt, "circle"),
+            MarkerShape::Square => write!(fmt, "square"),
+            MarkerShape::Bar => write!(fmt, "bar"),
+        }
+    }
+}
+
+/// Symbol drawn on the vertices of lines, line strings and polygons, e.g. arrowheads to show
+/// directions or dots to show vertices.
+///
+/// Markers are set in the [`Style`] of a document and defined in its `<defs>` when rendering:
+///
+/// ```
+/// # use geo::Line;
+/// # use geo_svg::{Marker, ToSvg};
+/// let flow = Line::new((0.0, 0.0), (10.0, 0.0));
+/// let svg = flow.to_svg().with_marker_end(Marker::arrow());
+/// assert_eq!(
+///     svg.svg_str(),
+///     concat!(
+///         r#"<defs><marker id="marker-arrow-3-stroke" viewBox="0 0 10 10" refX="10" refY="5" "#,
+///         r#"markerWidth="3" markerHeight="3" orient="auto-start-reverse">"#,
+///         r#"<path d="M 0 0 L 10 5 L 0 10 Z" fill="context-stroke"/></marker></defs>"#,
+///         r#"<path d="M 0.0 0.0 L 10.0 0.0" marker-end="url(#marker-arrow-3-stroke)"/>"#,
+///     )
+/// );
+/// ```
+///
+/// [`Style`]: crate::Style
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marker {
+    pub shape: MarkerShape,
+    /// size of the marker, in stroke widths
+    pub size: f32,
+    /// color of the marker, the one of the stroke when `None`
+    pub color: Option<Color>,
+}
+
+impl Marker {
+    pub fn new(shape: MarkerShape) -> Self {
+        Self {
+            shape,
+            size: 3.0,
+            color: None,
+        }
+    }
+
+    pub fn arrow() -> Self {
+        Self::new(MarkerShape::Arrow)
+    }
+
+    pub fn circle() -> Self {
+        Self::new(MarkerShape::Circle)
+    }
+
+    pub fn square() -> Self {
+        Self::new(MarkerShape::Square)
+    }
+
+    pub fn bar() -> Self {
+        Self::new(MarkerShape::Bar)
+    }
+
+    /// set the size of the marker, in stroke widths
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// id of the definition of this marker, the same for equal markers
+    pub fn id(&self) -> String {
+        let size = self.size.to_string().replace(['.', '-'], "_");
+        let color = match self.color {
+            None => "stroke".to_string(),
+            Some(color) => match color.to_hex() {
+                Some(Color::Hex(hex)) => format!("{hex:06X}"),
+                Some(Color::HexAlpha(hex)) => format!("{hex:08X}"),
+                _ => color
+                    .to_string()
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect(),
+            },
+        };
+        format!("marker-{}-{}-{}", self.shape, size, color)
+    }
+
+    /// `url(#id)` reference to this marker
+    pub(crate) fn url(&self) -> MarkerUrl {
+        MarkerUrl(self.id())
+    }
+}
+
+pub(crate) struct MarkerUrl(String);
+
+impl Display for MarkerUrl {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        write!(fmt, "url(#{})", self.0)
+    }
+}
+
+impl Display for Marker {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let ref_x = match self.shape {
+            MarkerShape::Arrow => 10,
+            _ => 5,
+        };
+        write!(
+            fmt,
+            r#"<marker id="{id}" viewBox="0 0 10 10" refX="{ref_x}" refY="5" markerWidth="{size}" markerHeight="{size}" orient="auto-start-reverse">"#,
+            id = self.id(),
+            size = self.size,
+        )?;
+        let fill = match self.color {
+            Some(color) => color.to_string(),
+            None => "context-stroke".to_string(),
+        };
+        match self.shape {
+            MarkerShape::Arrow => {
+                write!(fmt, r#"<path d="M 0 0 L 10 5 L 0 10 Z" fill="{fill}"/>"#)?
+            }
+            MarkerShape::Circle => write!(fmt, r#"<circle cx="5" cy="5" r="5" fill="{fill}"/>"#)?,
+            MarkerShape::Square => write!(fmt, r#"<rect width="10" height="10" fill="{fill}"/>"#)?,
+            MarkerShape::Bar => {
+                write!(fmt, r#"<rect x="4" width="2" height="10" fill="{fill}"/>"#)?
+            }
+        }
+        fmt.write_str("</marker>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id() {
+        assert_eq!(Marker::circle().id(), "marker-circle-3-stroke");
+        assert_eq!(
+            Marker::bar()
+                .with_size(1.5)
+                .with_color(Color::Named("red"))
+                .id(),
+            "marker-bar-1_5-FF0000"
+        );
+        assert_eq!(
+            Marker::square().with_color(Color::Named("unknown")).id(),
+            "marker-square-3-unknown"
+        );
+    }
+
+    #[test]
+    fn test_definition() {
+        let marker = Marker::circle()
+            .with_size(2.0)
+            .with_color(Color::Hex(0x00FF00));
+        assert_eq!(
+            marker.to_string(),
+            r##"<marker id="marker-circle-2-00FF00" viewBox="0 0 10 10" refX="5" refY="5" markerWidth="2" markerHeight="2" orient="auto-start-reverse"><circle cx="5" cy="5" r="5" fill="#00FF00"/></marker>"##
+        );
+    }
+}
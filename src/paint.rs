@@ -0,0 +1,238 @@
+use crate::{precision::DisplayNumber, svg::Escaped, Color, ColorRamp, Precision};
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// Paint of a fill or a stroke: a solid color, or a paint server like a [`Gradient`] defined in
+/// the document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Color(Color),
+    /// reference to the paint server with the given id, written as `url(#id)`
+    Url(String),
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
+}
+
+impl Display for Paint {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Paint::Color(color) => write!(fmt, "{}", color),
+            Paint::Url(id) => write!(fmt, "url(#{})", Escaped(id)),
+        }
+    }
+}
+
+/// Coordinate system of the attributes of a [`Gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientUnits {
+    /// fractions of the bounding box of each painted element, from 0 to 1
+    ObjectBoundingBox,
+    /// document coordinates, so the gradient spans all the elements it paints
+    UserSpaceOnUse,
+}
+
+impl Display for GradientUnits {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            GradientUnits::ObjectBoundingBox => write!(fmt, "objectBoundingBox"),
+            GradientUnits::UserSpaceOnUse => write!(fmt, "userSpaceOnUse"),
+        }
+    }
+}
+
+/// Geometry of a [`Gradient`], in its [`GradientUnits`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientShape {
+    /// colors change along the vector from `start` to `end`
+    Linear { start: (f64, f64), end: (f64, f64) },
+    /// colors change from the `focus` to the circle of the given `center` and `radius`
+    Radial {
+        center: (f64, f64),
+        radius: f64,
+        focus: (f64, f64),
+    },
+}
+
+/// Linear or radial gradient, defined once in the `<defs>` of a document and referenced by its
+/// id from the fill or stroke of any number of elements.
+///
+/// ```
+/// # use geo::Rect;
+/// # use geo_svg::{Color, Gradient, ToSvg};
+/// let area = Rect::new((0.0, 0.0), (10.0, 10.0));
+/// let svg = area.to_svg().with_fill_gradient(
+///     Gradient::linear("heat", (0.0, 0.0), (1.0, 0.0))
+///         .with_stop(0.0, Color::YELLOW)
+///         .with_stop(1.0, Color::RED),
+/// );
+/// assert_eq!(
+///     svg.svg_str(),
+///     concat!(
+///         r##"<defs><linearGradient id="heat" gradientUnits="objectBoundingBox" x1="0" y1="0" x2="1" y2="0">"##,
+///         r##"<stop offset="0" stop-color="yellow"/><stop offset="1" stop-color="red"/></linearGradient></defs>"##,
+///         r##"<path fill-rule="evenodd" d="M 0.0 0.0 L 10.0 0.0 L 10.0 10.0 L 0.0 10.0 L 0.0 0.0 Z" fill="url(#heat)"/>"##,
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub id: String,
+    pub shape: GradientShape,
+    pub units: GradientUnits,
+    /// offsets between 0 and 1 along the gradient, in increasing order, and their colors
+    pub stops: Vec<(f64, Color)>,
+}
+
+impl Gradient {
+    /// gradient along the vector from `start` to `end`, as fractions of the bounding box of the
+    /// painted elements until set otherwise with [`with_units`]
+    ///
+    /// [`with_units`]: Gradient::with_units
+    pub fn linear(id: impl Into<String>, start: (f64, f64), end: (f64, f64)) -> Self {
+        Self {
+            id: id.into(),
+            shape: GradientShape::Linear { start, end },
+            units: GradientUnits::ObjectBoundingBox,
+            stops: vec![],
+        }
+    }
+
+    /// gradient from `center` outwards to `radius`, as fractions of the bounding box of the
+    /// painted elements until set otherwise with [`with_units`]
+    ///
+    /// [`with_units`]: Gradient::with_units
+    pub fn radial(id: impl Into<String>, center: (f64, f64), radius: f64) -> Self {
+        Self {
+            id: id.into(),
+            shape: GradientShape::Radial {
+                center,
+                radius,
+                focus: center,
+            },
+            units: GradientUnits::ObjectBoundingBox,
+            stops: vec![],
+        }
+    }
+
+    pub fn with_units(mut self, units: GradientUnits) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// move the point a radial gradient starts from away from its center
+    pub fn with_focus(mut self, focus: (f64, f64)) -> Self {
+        if let GradientShape::Radial { focus: old, .. } = &mut self.shape {
+            *old = focus;
+        }
+        self
+    }
+
+    /// add a stop of `color` at `offset`, between 0 and 1
+    pub fn with_stop(mut self, offset: f64, color: Color) -> Self {
+        self.stops.push((offset, color));
+        self
+    }
+
+    /// replace the stops of this gradient with the ones of `ramp`
+    pub fn with_ramp(mut self, ramp: &ColorRamp) -> Self {
+        self.stops = ramp.stops.clone();
+        self
+    }
+
+    /// paint referencing this gradient
+    pub fn paint(&self) -> Paint {
+        Paint::Url(self.id.clone())
+    }
+
+    /// write this gradient with its numbers rounded to `precision`
+    pub(crate) fn write(&self, fmt: &mut dyn Write, precision: Option<Precision>) -> Result {
+        let number = |value: f64| DisplayNumber(value, precision);
+        let element = match self.shape {
+            GradientShape::Linear { .. } => "linearGradient",
+            GradientShape::Radial { .. } => "radialGradient",
+        };
+        write!(
+            fmt,
+            r#"<{element} id="{id}" gradientUnits="{units}""#,
+            id = Escaped(&self.id),
+            units = self.units,
+        )?;
+        match self.shape {
+            GradientShape::Linear {
+                start: (x1, y1),
+                end: (x2, y2),
+            } => write!(
+                fmt,
+                r#" x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}">"#,
+                x1 = number(x1),
+                y1 = number(y1),
+                x2 = number(x2),
+                y2 = number(y2),
+            )?,
+            GradientShape::Radial {
+                center: (cx, cy),
+                radius,
+                focus: (fx, fy),
+            } => {
+                write!(
+                    fmt,
+                    r#" cx="{}" cy="{}" r="{}""#,
+                    number(cx),
+                    number(cy),
+                    number(radius),
+                )?;
+                if (fx, fy) != (cx, cy) {
+                    write!(fmt, r#" fx="{}" fy="{}""#, number(fx), number(fy))?;
+                }
+                fmt.write_str(">")?;
+            }
+        }
+        for (offset, color) in &self.stops {
+            write!(
+                fmt,
+                r#"<stop offset="{offset}" stop-color="{color}"/>"#,
+                offset = number(*offset),
+            )?;
+        }
+        write!(fmt, "</{element}>")
+    }
+}
+
+impl Display for Gradient {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        self.write(fmt, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette;
+
+    #[test]
+    fn test_radial() {
+        let gradient = Gradient::radial("glow", (50.0, 50.0), 25.0)
+            .with_units(GradientUnits::UserSpaceOnUse)
+            .with_focus((40.0, 50.0))
+            .with_stop(0.0, Color::WHITE)
+            .with_stop(1.0, Color::Rgba(0, 0, 255, 0.5));
+        assert_eq!(
+            gradient.to_string(),
+            r#"<radialGradient id="glow" gradientUnits="userSpaceOnUse" cx="50" cy="50" r="25" fx="40" fy="50"><stop offset="0" stop-color="white"/><stop offset="1" stop-color="rgba(0,0,255,0.5)"/></radialGradient>"#
+        );
+    }
+
+    #[test]
+    fn test_ramp() {
+        let gradient = Gradient::linear("elevation", (0.0, 1.0), (0.0, 0.0))
+            .with_ramp(&ColorRamp::new(&palette::VIRIDIS[..2]));
+        assert_eq!(
+            gradient.to_string(),
+            r##"<linearGradient id="elevation" gradientUnits="objectBoundingBox" x1="0" y1="1" x2="0" y2="0"><stop offset="0" stop-color="#440154"/><stop offset="1" stop-color="#472D7B"/></linearGradient>"##
+        );
+        assert_eq!(gradient.paint().to_string(), "url(#elevation)");
+    }
+}
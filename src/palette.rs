@@ -0,0 +1,229 @@
+//! Built-in palettes, usable as stops of a [`ColorRamp`] or directly as lists of categorical
+//! colors.
+//!
+//! The sequential palettes go from light to dark, except the perceptually uniform ones from
+//! matplotlib which go from dark to light; the diverging palettes have a neutral middle color;
+//! the qualitative palettes are meant for categories and shouldn't be interpolated.
+//!
+//! The ColorBrewer palettes are by Cynthia Brewer, see <https://colorbrewer2.org>.
+//!
+//! [`ColorRamp`]: crate::ColorRamp
+
+use crate::Color;
+
+/// viridis, perceptually uniform from dark purple to yellow
+pub const VIRIDIS: &[Color] = &[
+    Color::Hex(0x440154),
+    Color::Hex(0x472D7B),
+    Color::Hex(0x3B528B),
+    Color::Hex(0x2C728E),
+    Color::Hex(0x21918C),
+    Color::Hex(0x28AE80),
+    Color::Hex(0x5EC962),
+    Color::Hex(0xADDC30),
+    Color::Hex(0xFDE725),
+];
+
+/// magma, perceptually uniform from black to light yellow
+pub const MAGMA: &[Color] = &[
+    Color::Hex(0x000004),
+    Color::Hex(0x1C1044),
+    Color::Hex(0x4F127B),
+    Color::Hex(0x812581),
+    Color::Hex(0xB5367A),
+    Color::Hex(0xE55064),
+    Color::Hex(0xFB8761),
+    Color::Hex(0xFEC287),
+    Color::Hex(0xFCFDBF),
+];
+
+/// plasma, perceptually uniform from dark blue to yellow
+pub const PLASMA: &[Color] = &[
+    Color::Hex(0x0D0887),
+    Color::Hex(0x4C02A1),
+    Color::Hex(0x7E03A8),
+    Color::Hex(0xA92395),
+    Color::Hex(0xCC4778),
+    Color::Hex(0xE56B5D),
+    Color::Hex(0xF89441),
+    Color::Hex(0xFDC328),
+    Color::Hex(0xF0F921),
+];
+
+/// ColorBrewer sequential Blues
+pub const BLUES: &[Color] = &[
+    Color::Hex(0xF7FBFF),
+    Color::Hex(0xDEEBF7),
+    Color::Hex(0xC6DBEF),
+    Color::Hex(0x9ECAE1),
+    Color::Hex(0x6BAED6),
+    Color::Hex(0x4292C6),
+    Color::Hex(0x2171B5),
+    Color::Hex(0x08519C),
+    Color::Hex(0x08306B),
+];
+
+/// ColorBrewer sequential Greens
+pub const GREENS: &[Color] = &[
+    Color::Hex(0xF7FCF5),
+    Color::Hex(0xE5F5E0),
+    Color::Hex(0xC7E9C0),
+    Color::Hex(0xA1D99B),
+    Color::Hex(0x74C476),
+    Color::Hex(0x41AB5D),
+    Color::Hex(0x238B45),
+    Color::Hex(0x006D2C),
+    Color::Hex(0x00441B),
+];
+
+/// ColorBrewer sequential Reds
+pub const REDS: &[Color] = &[
+    Color::Hex(0xFFF5F0),
+    Color::Hex(0xFEE0D2),
+    Color::Hex(0xFCBBA1),
+    Color::Hex(0xFC9272),
+    Color::Hex(0xFB6A4A),
+    Color::Hex(0xEF3B2C),
+    Color::Hex(0xCB181D),
+    Color::Hex(0xA50F15),
+    Color::Hex(0x67000D),
+];
+
+/// ColorBrewer sequential YlOrRd
+pub const YL_OR_RD: &[Color] = &[
+    Color::Hex(0xFFFFCC),
+    Color::Hex(0xFFEDA0),
+    Color::Hex(0xFED976),
+    Color::Hex(0xFEB24C),
+    Color::Hex(0xFD8D3C),
+    Color::Hex(0xFC4E2A),
+    Color::Hex(0xE31A1C),
+    Color::Hex(0xBD0026),
+    Color::Hex(0x800026),
+];
+
+/// ColorBrewer sequential YlGnBu
+pub const YL_GN_BU: &[Color] = &[
+    Color::Hex(0xFFFFD9),
+    Color::Hex(0xEDF8B1),
+    Color::Hex(0xC7E9B4),
+    Color::Hex(0x7FCDBB),
+    Color::Hex(0x41B6C4),
+    Color::Hex(0x1D91C0),
+    Color::Hex(0x225EA8),
+    Color::Hex(0x253494),
+    Color::Hex(0x081D58),
+];
+
+/// ColorBrewer diverging RdBu
+pub const RD_BU: &[Color] = &[
+    Color::Hex(0x67001F),
+    Color::Hex(0xB2182B),
+    Color::Hex(0xD6604D),
+    Color::Hex(0xF4A582),
+    Color::Hex(0xFDDBC7),
+    Color::Hex(0xF7F7F7),
+    Color::Hex(0xD1E5F0),
+    Color::Hex(0x92C5DE),
+    Color::Hex(0x4393C3),
+    Color::Hex(0x2166AC),
+    Color::Hex(0x053061),
+];
+
+/// ColorBrewer diverging RdYlGn
+pub const RD_YL_GN: &[Color] = &[
+    Color::Hex(0xA50026),
+    Color::Hex(0xD73027),
+    Color::Hex(0xF46D43),
+    Color::Hex(0xFDAE61),
+    Color::Hex(0xFEE08B),
+    Color::Hex(0xFFFFBF),
+    Color::Hex(0xD9EF8B),
+    Color::Hex(0xA6D96A),
+    Color::Hex(0x66BD63),
+    Color::Hex(0x1A9850),
+    Color::Hex(0x006837),
+];
+
+/// ColorBrewer diverging Spectral
+pub const SPECTRAL: &[Color] = &[
+    Color::Hex(0x9E0142),
+    Color::Hex(0xD53E4F),
+    Color::Hex(0xF46D43),
+    Color::Hex(0xFDAE61),
+    Color::Hex(0xFEE08B),
+    Color::Hex(0xFFFFBF),
+    Color::Hex(0xE6F598),
+    Color::Hex(0xABDDA4),
+    Color::Hex(0x66C2A5),
+    Color::Hex(0x3288BD),
+    Color::Hex(0x5E4FA2),
+];
+
+/// ColorBrewer diverging BrBG
+pub const BR_BG: &[Color] = &[
+    Color::Hex(0x543005),
+    Color::Hex(0x8C510A),
+    Color::Hex(0xBF812D),
+    Color::Hex(0xDFC27D),
+    Color::Hex(0xF6E8C3),
+    Color::Hex(0xF5F5F5),
+    Color::Hex(0xC7EAE5),
+    Color::Hex(0x80CDC1),
+    Color::Hex(0x35978F),
+    Color::Hex(0x01665E),
+    Color::Hex(0x003C30),
+];
+
+/// ColorBrewer qualitative Set1
+pub const SET1: &[Color] = &[
+    Color::Hex(0xE41A1C),
+    Color::Hex(0x377EB8),
+    Color::Hex(0x4DAF4A),
+    Color::Hex(0x984EA3),
+    Color::Hex(0xFF7F00),
+    Color::Hex(0xFFFF33),
+    Color::Hex(0xA65628),
+    Color::Hex(0xF781BF),
+    Color::Hex(0x999999),
+];
+
+/// ColorBrewer qualitative Set2
+pub const SET2: &[Color] = &[
+    Color::Hex(0x66C2A5),
+    Color::Hex(0xFC8D62),
+    Color::Hex(0x8DA0CB),
+    Color::Hex(0xE78AC3),
+    Color::Hex(0xA6D854),
+    Color::Hex(0xFFD92F),
+    Color::Hex(0xE5C494),
+    Color::Hex(0xB3B3B3),
+];
+
+/// ColorBrewer qualitative Dark2
+pub const DARK2: &[Color] = &[
+    Color::Hex(0x1B9E77),
+    Color::Hex(0xD95F02),
+    Color::Hex(0x7570B3),
+    Color::Hex(0xE7298A),
+    Color::Hex(0x66A61E),
+    Color::Hex(0xE6AB02),
+    Color::Hex(0xA6761D),
+    Color::Hex(0x666666),
+];
+
+/// ColorBrewer qualitative Paired
+pub const PAIRED: &[Color] = &[
+    Color::Hex(0xA6CEE3),
+    Color::Hex(0x1F78B4),
+    Color::Hex(0xB2DF8A),
+    Color::Hex(0x33A02C),
+    Color::Hex(0xFB9A99),
+    Color::Hex(0xE31A1C),
+    Color::Hex(0xFDBF6F),
+    Color::Hex(0xFF7F00),
+    Color::Hex(0xCAB2D6),
+    Color::Hex(0x6A3D9A),
+    Color::Hex(0xFFFF99),
+    Color::Hex(0xB15928),
+];
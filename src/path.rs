@@ -0,0 +1,171 @@
+use crate::{
+    precision::{DisplayNumber, Number},
+    Precision, Style,
+};
+use geo::{Coord, CoordNum};
+use num_traits::NumCast;
+use std::fmt::{Result, Write};
+
+/// Writes the `d` attribute of a `<path>` element, either with absolute `M`/`L` commands or,
+/// when [`Style::compact`] is set, without redundant letters and whitespace.
+///
+/// Compact paths use relative commands only with [`Precision::Decimals`], whose rounded
+/// coordinates have exact differences, other precisions would let the rounding of each offset
+/// drift along the path.
+pub(crate) struct PathData<'a> {
+    writer: &'a mut dyn Write,
+    precision: Option<Precision>,
+    compact: bool,
+    relative: bool,
+    current: Coord<f64>,
+    start: Coord<f64>,
+    command: Option<char>,
+    empty: bool,
+}
+
+impl<'a> PathData<'a> {
+    pub(crate) fn new(writer: &'a mut dyn Write, style: &Style) -> Self {
+        Self {
+            writer,
+            precision: style.precision,
+            compact: style.compact.unwrap_or(false),
+            relative: style.compact.unwrap_or(false)
+                && matches!(style.precision, Some(Precision::Decimals(_))),
+            current: Coord::zero(),
+            start: Coord::zero(),
+            command: None,
+            empty: true,
+        }
+    }
+
+    /// write one subpath going through `coords`, closing it with `Z` when `close` is set
+    pub(crate) fn ring<T: CoordNum>(&mut self, coords: &[Coord<T>], close: bool) -> Result {
+        let coords = match coords {
+            // the closing segment is implied by `z` in compact mode
+            [first, rest @ .., last] if self.compact && close && first == last => {
+                &coords[..rest.len() + 1]
+            }
+            _ => coords,
+        };
+        let Some((first, rest)) = coords.split_first() else {
+            return Ok(());
+        };
+        if self.compact {
+            self.compact_move_to(*first)?;
+            for coord in rest {
+                self.compact_line_to(*coord)?;
+            }
+            if close {
+                self.writer.write_char('z')?;
+                self.command = Some('z');
+                self.current = self.start;
+            }
+        } else {
+            let separator = if self.empty { "" } else { " " };
+            write!(
+                self.writer,
+                "{separator}M {x} {y}",
+                x = Number(first.x, self.precision),
+                y = Number(first.y, self.precision),
+            )?;
+            for coord in rest {
+                write!(
+                    self.writer,
+                    " L {x} {y}",
+                    x = Number(coord.x, self.precision),
+                    y = Number(coord.y, self.precision),
+                )?;
+            }
+            if close {
+                self.writer.write_str(" Z")?;
+            }
+        }
+        self.empty = false;
+        Ok(())
+    }
+
+    fn compact_move_to<T: CoordNum>(&mut self, coord: Coord<T>) -> Result {
+        let coord = self.coord(coord);
+        if self.empty || !self.relative {
+            self.command('M', &[coord.x, coord.y])?;
+            // further coordinate pairs are implicit absolute line-tos
+            self.command = Some('L');
+        } else {
+            let delta = self.delta(coord);
+            self.command('m', &[delta.x, delta.y])?;
+            // further coordinate pairs are implicit relative line-tos
+            self.command = Some('l');
+        }
+        self.current = coord;
+        self.start = coord;
+        Ok(())
+    }
+
+    fn compact_line_to<T: CoordNum>(&mut self, coord: Coord<T>) -> Result {
+        let coord = self.coord(coord);
+        if !self.relative {
+            if coord.y == self.current.y {
+                self.command('H', &[coord.x])?;
+            } else if coord.x == self.current.x {
+                self.command('V', &[coord.y])?;
+            } else {
+                self.command('L', &[coord.x, coord.y])?;
+            }
+            self.current = coord;
+            return Ok(());
+        }
+        let delta = self.delta(coord);
+        if delta.y == 0.0 {
+            self.command('h', &[delta.x])?;
+        } else if delta.x == 0.0 {
+            self.command('v', &[delta.y])?;
+        } else {
+            self.command('l', &[delta.x, delta.y])?;
+        }
+        self.current = coord;
+        Ok(())
+    }
+
+    /// write `numbers`, preceded by the `command` letter unless it repeats the previous command
+    fn command(&mut self, command: char, numbers: &[f64]) -> Result {
+        let mut separate = true;
+        if self.command != Some(command) {
+            self.writer.write_char(command)?;
+            self.command = Some(command);
+            separate = false;
+        }
+        for number in numbers {
+            // a minus sign already separates two numbers
+            if separate && *number >= 0.0 {
+                self.writer.write_char(' ')?;
+            }
+            write!(self.writer, "{}", DisplayNumber(*number, self.precision))?;
+            separate = true;
+        }
+        Ok(())
+    }
+
+    /// convert `coord` to the value it will be written with, so relative commands don't
+    /// accumulate rounding errors
+    fn coord<T: CoordNum>(&self, coord: Coord<T>) -> Coord<f64> {
+        Coord {
+            x: self.round(NumCast::from(coord.x).unwrap_or(f64::NAN)),
+            y: self.round(NumCast::from(coord.y).unwrap_or(f64::NAN)),
+        }
+    }
+
+    /// offset from the current point to `coord`
+    fn delta(&self, coord: Coord<f64>) -> Coord<f64> {
+        Coord {
+            x: self.round(coord.x - self.current.x),
+            y: self.round(coord.y - self.current.y),
+        }
+    }
+
+    fn round(&self, value: f64) -> f64 {
+        match self.precision {
+            Some(precision) => precision.round(value).map_or(value, |(value, _)| value),
+            None => value,
+        }
+    }
+}
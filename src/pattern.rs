@@ -0,0 +1,223 @@
+use crate::{precision::DisplayNumber, svg::Escaped, Color, Paint, Precision};
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// Content repeated by a [`Pattern`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternTile {
+    /// parallel lines
+    Hatch,
+    /// two sets of perpendicular lines
+    CrossHatch,
+    /// one dot per tile, with a diameter of the line width
+    Dots,
+    /// SVG elements drawn in a tile of the given size, ignoring the spacing and color of the
+    /// pattern
+    Custom {
+        width: f64,
+        height: f64,
+        content: String,
+    },
+}
+
+/// Tiled fill, defined once in the `<defs>` of a document and referenced by its id from the
+/// fill of any number of elements, e.g. to distinguish polygons without relying on colors:
+///
+/// ```
+/// # use geo::Rect;
+/// # use geo_svg::{Color, Pattern, ToSvg};
+/// let wetland = Rect::new((0.0, 0.0), (10.0, 10.0));
+/// let svg = wetland
+///     .to_svg()
+///     .with_fill_pattern(Pattern::hatch("wetland").with_color(Color::BLUE))
+///     .with_stroke_color(Color::BLUE);
+/// assert_eq!(
+///     svg.svg_str(),
+///     concat!(
+///         r#"<defs><pattern id="wetland" patternUnits="userSpaceOnUse" width="4" height="4" patternTransform="rotate(45)">"#,
+///         r#"<path d="M 0 2 L 4 2" stroke="blue" stroke-width="1"/></pattern></defs>"#,
+///         r#"<path fill-rule="evenodd" d="M 0.0 0.0 L 10.0 0.0 L 10.0 10.0 L 0.0 10.0 L 0.0 0.0 Z" fill="url(#wetland)" stroke="blue"/>"#,
+///     )
+/// );
+/// ```
+///
+/// Sizes are in document units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub id: String,
+    pub tile: PatternTile,
+    /// distance between the lines or dots
+    pub spacing: f64,
+    /// rotation of the tiles in degrees, clockwise unless the document flips its y axis
+    pub angle: f64,
+    /// width of the lines, or diameter of the dots
+    pub line_width: f64,
+    pub color: Color,
+    /// color filling the tiles behind the lines or dots, transparent when `None`
+    pub background: Option<Color>,
+}
+
+impl Pattern {
+    fn new(id: impl Into<String>, tile: PatternTile, angle: f64) -> Self {
+        Self {
+            id: id.into(),
+            tile,
+            spacing: 4.0,
+            angle,
+            line_width: 1.0,
+            color: Color::BLACK,
+            background: None,
+        }
+    }
+
+    /// diagonal lines
+    pub fn hatch(id: impl Into<String>) -> Self {
+        Self::new(id, PatternTile::Hatch, 45.0)
+    }
+
+    /// diagonal cross-hatch
+    pub fn cross_hatch(id: impl Into<String>) -> Self {
+        Self::new(id, PatternTile::CrossHatch, 45.0)
+    }
+
+    /// grid of dots
+    pub fn dots(id: impl Into<String>) -> Self {
+        Self::new(id, PatternTile::Dots, 0.0)
+    }
+
+    /// tiles of `width` and `height` made of the SVG elements of `content`
+    pub fn custom(
+        id: impl Into<String>,
+        width: f64,
+        height: f64,
+        content: impl Into<String>,
+    ) -> Self {
+        let tile = PatternTile::Custom {
+            width,
+            height,
+            content: content.into(),
+        };
+        Self::new(id, tile, 0.0)
+    }
+
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// set the rotation of the tiles in degrees
+    pub fn with_angle(mut self, angle: f64) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_line_width(mut self, line_width: f64) -> Self {
+        self.line_width = line_width;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// paint referencing this pattern
+    pub fn paint(&self) -> Paint {
+        Paint::Url(self.id.clone())
+    }
+
+    /// write this pattern with its numbers rounded to `precision`
+    pub(crate) fn write(&self, fmt: &mut dyn Write, precision: Option<Precision>) -> Result {
+        let number = |value: f64| DisplayNumber(value, precision);
+        let (width, height) = match self.tile {
+            PatternTile::Custom { width, height, .. } => (width, height),
+            _ => (self.spacing, self.spacing),
+        };
+        let (width, height) = (number(width), number(height));
+        write!(
+            fmt,
+            r#"<pattern id="{id}" patternUnits="userSpaceOnUse" width="{width}" height="{height}""#,
+            id = Escaped(&self.id),
+        )?;
+        if self.angle != 0.0 {
+            write!(fmt, r#" patternTransform="rotate({})""#, number(self.angle))?;
+        }
+        fmt.write_str(">")?;
+        if let Some(background) = self.background {
+            write!(
+                fmt,
+                r#"<rect width="{width}" height="{height}" fill="{background}"/>"#
+            )?;
+        }
+        let (color, line_width, middle) = (
+            self.color,
+            number(self.line_width),
+            number(self.spacing / 2.0),
+        );
+        match &self.tile {
+            PatternTile::Hatch => write!(
+                fmt,
+                r#"<path d="M 0 {middle} L {width} {middle}" stroke="{color}" stroke-width="{line_width}"/>"#
+            )?,
+            PatternTile::CrossHatch => write!(
+                fmt,
+                r#"<path d="M 0 {middle} L {width} {middle} M {middle} 0 L {middle} {height}" stroke="{color}" stroke-width="{line_width}"/>"#
+            )?,
+            PatternTile::Dots => write!(
+                fmt,
+                r#"<circle cx="{middle}" cy="{middle}" r="{radius}" fill="{color}"/>"#,
+                radius = number(self.line_width / 2.0),
+            )?,
+            PatternTile::Custom { content, .. } => fmt.write_str(content)?,
+        }
+        fmt.write_str("</pattern>")
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        self.write(fmt, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_hatch() {
+        let pattern = Pattern::cross_hatch("grid")
+            .with_spacing(10.0)
+            .with_angle(30.0)
+            .with_line_width(0.5)
+            .with_background(Color::WHITE);
+        assert_eq!(
+            pattern.to_string(),
+            r#"<pattern id="grid" patternUnits="userSpaceOnUse" width="10" height="10" patternTransform="rotate(30)"><rect width="10" height="10" fill="white"/><path d="M 0 5 L 10 5 M 5 0 L 5 10" stroke="black" stroke-width="0.5"/></pattern>"#
+        );
+    }
+
+    #[test]
+    fn test_dots() {
+        let pattern = Pattern::dots("sand")
+            .with_line_width(2.0)
+            .with_color(Color::TAN);
+        assert_eq!(
+            pattern.to_string(),
+            r#"<pattern id="sand" patternUnits="userSpaceOnUse" width="4" height="4"><circle cx="2" cy="2" r="1" fill="tan"/></pattern>"#
+        );
+    }
+
+    #[test]
+    fn test_custom() {
+        let pattern = Pattern::custom("marsh", 6.0, 3.0, r#"<path d="M 1 2 L 5 2"/>"#);
+        assert_eq!(
+            pattern.to_string(),
+            r#"<pattern id="marsh" patternUnits="userSpaceOnUse" width="6" height="3"><path d="M 1 2 L 5 2"/></pattern>"#
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use geo::{Area, BoundingRect, Centroid, Coord, CoordNum, LineString, MultiPolygon, Polygon};
+use num_traits::NumCast;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Shapes with a visual center, the point inside them farthest from their outline, where a
+/// label is best placed.
+///
+/// The center is found with the pole of inaccessibility algorithm, see
+/// <https://blog.mapbox.com/a-new-algorithm-for-finding-a-visual-center-of-a-polygon-7c77e6492fbc>.
+/// Unlike the centroid it lies inside concave shapes:
+///
+/// ```
+/// # use geo::{polygon, Coord};
+/// # use geo_svg::VisualCenter;
+/// let l_shape = polygon![
+///     (x: 0.0, y: 0.0),
+///     (x: 10.0, y: 0.0),
+///     (x: 10.0, y: 2.0),
+///     (x: 2.0, y: 2.0),
+///     (x: 2.0, y: 10.0),
+///     (x: 0.0, y: 10.0),
+/// ];
+/// let center = l_shape.visual_center(0.01).unwrap();
+/// assert!(center.x < 2.0 || center.y < 2.0);
+/// ```
+pub trait VisualCenter<C: CoordNum> {
+    /// the visual center of this shape, found to within `precision` in document units, or `None`
+    /// if the shape is empty
+    fn visual_center(&self, precision: f64) -> Option<Coord<C>>;
+
+    /// the visual center of this shape, found to within a thousandth of its larger side
+    fn default_visual_center(&self) -> Option<Coord<C>>;
+}
+
+impl<C: CoordNum> VisualCenter<C> for Polygon<C> {
+    fn visual_center(&self, precision: f64) -> Option<Coord<C>> {
+        let center = pole_of_inaccessibility(&to_f64(self), precision)?;
+        Some(Coord {
+            x: NumCast::from(center.x)?,
+            y: NumCast::from(center.y)?,
+        })
+    }
+
+    fn default_visual_center(&self) -> Option<Coord<C>> {
+        let rect = to_f64(self).bounding_rect()?;
+        self.visual_center(rect.width().max(rect.height()) * 0.001)
+    }
+}
+
+/// the visual center of the largest polygon
+impl<C: CoordNum> VisualCenter<C> for MultiPolygon<C> {
+    fn visual_center(&self, precision: f64) -> Option<Coord<C>> {
+        largest(self)?.visual_center(precision)
+    }
+
+    fn default_visual_center(&self) -> Option<Coord<C>> {
+        largest(self)?.default_visual_center()
+    }
+}
+
+fn largest<C: CoordNum>(multi_polygon: &MultiPolygon<C>) -> Option<&Polygon<C>> {
+    multi_polygon.iter().max_by(|a, b| {
+        let (a, b) = (to_f64(a).unsigned_area(), to_f64(b).unsigned_area());
+        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+    })
+}
+
+fn to_f64<C: CoordNum>(polygon: &Polygon<C>) -> Polygon<f64> {
+    let ring = |ring: &LineString<C>| {
+        ring.coords()
+            .map(|coord| Coord {
+                x: NumCast::from(coord.x).unwrap_or(f64::NAN),
+                y: NumCast::from(coord.y).unwrap_or(f64::NAN),
+            })
+            .collect()
+    };
+    Polygon::new(
+        ring(polygon.exterior()),
+        polygon.interiors().iter().map(ring).collect(),
+    )
+}
+
+/// Square cell of the search, ordered by the largest distance to the outline a point of the
+/// cell can have.
+struct Cell {
+    center: Coord<f64>,
+    half_size: f64,
+    /// signed distance from the center to the outline, negative outside the polygon
+    distance: f64,
+}
+
+impl Cell {
+    fn new(center: Coord<f64>, half_size: f64, polygon: &Polygon<f64>) -> Self {
+        Self {
+            center,
+            half_size,
+            distance: signed_distance(center, polygon),
+        }
+    }
+
+    fn max_distance(&self) -> f64 {
+        self.distance + self.half_size * std::f64::consts::SQRT_2
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.max_distance().total_cmp(&other.max_distance())
+    }
+}
+
+fn pole_of_inaccessibility(polygon: &Polygon<f64>, precision: f64) -> Option<Coord<f64>> {
+    let rect = polygon.bounding_rect()?;
+    let cell_size = rect.width().min(rect.height());
+    if cell_size <= 0.0 || !cell_size.is_finite() {
+        return Some(rect.min());
+    }
+    let half_size = cell_size / 2.0;
+    let mut cells = BinaryHeap::new();
+    let mut x = rect.min().x;
+    while x < rect.max().x {
+        let mut y = rect.min().y;
+        while y < rect.max().y {
+            let center = Coord {
+                x: x + half_size,
+                y: y + half_size,
+            };
+            cells.push(Cell::new(center, half_size, polygon));
+            y += cell_size;
+        }
+        x += cell_size;
+    }
+    let mut best = Cell::new(rect.center(), 0.0, polygon);
+    if let Some(centroid) = polygon.centroid() {
+        let centroid = Cell::new(centroid.0, 0.0, polygon);
+        if centroid.distance > best.distance {
+            best = centroid;
+        }
+    }
+    // cells can't get smaller than floating point numbers can tell apart
+    let precision = precision.max(cell_size * 1e-9);
+    while let Some(cell) = cells.pop() {
+        let (max_distance, center, half_size) =
+            (cell.max_distance(), cell.center, cell.half_size / 2.0);
+        if cell.distance > best.distance {
+            best = cell;
+        }
+        if max_distance - best.distance <= precision {
+            // no cell left can be better by more than the precision
+            break;
+        }
+        for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            let center = Coord {
+                x: center.x + dx * half_size,
+                y: center.y + dy * half_size,
+            };
+            cells.push(Cell::new(center, half_size, polygon));
+        }
+    }
+    Some(best.center)
+}
+
+/// distance from `point` to the closest ring of `polygon`, negative outside the polygon
+fn signed_distance(point: Coord<f64>, polygon: &Polygon<f64>) -> f64 {
+    let mut inside = false;
+    let mut distance = f64::INFINITY;
+    for ring in std::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+        for line in ring.lines() {
+            let (a, b) = (line.start, line.end);
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+            {
+                inside = !inside;
+            }
+            distance = distance.min(segment_distance(point, a, b));
+        }
+    }
+    if inside {
+        distance
+    } else {
+        -distance
+    }
+}
+
+fn segment_distance(point: Coord<f64>, a: Coord<f64>, b: Coord<f64>) -> f64 {
+    let segment = b - a;
+    let length = segment.x * segment.x + segment.y * segment.y;
+    let t = if length > 0.0 {
+        (((point.x - a.x) * segment.x + (point.y - a.y) * segment.y) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = a + segment * t;
+    ((point.x - closest.x).powi(2) + (point.y - closest.y).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{polygon, Rect};
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::new((0.0, 0.0), (10.0, 4.0)).to_polygon();
+        let center: Coord<f64> = rect.visual_center(0.01).unwrap();
+        assert!((center.y - 2.0).abs() < 0.01);
+        assert!((2.0..=8.0).contains(&center.x));
+    }
+
+    #[test]
+    fn test_hole() {
+        // the centroid of a square ring is in its hole, and its widest parts are its corners
+        let ring = polygon!(
+            exterior: [
+                (x: 0.0, y: 0.0),
+                (x: 10.0, y: 0.0),
+                (x: 10.0, y: 10.0),
+                (x: 0.0, y: 10.0),
+            ],
+            interiors: [[
+                (x: 2.0, y: 2.0),
+                (x: 8.0, y: 2.0),
+                (x: 8.0, y: 8.0),
+                (x: 2.0, y: 8.0),
+            ]],
+        );
+        let center = ring.default_visual_center().unwrap();
+        let widest = 2.0 * 2f64.sqrt() / (1.0 + 2f64.sqrt());
+        assert!((signed_distance(center, &ring) - widest).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_largest_part() {
+        let small = Rect::new((0.0, 0.0), (1.0, 1.0)).to_polygon();
+        let large = Rect::new((10.0, 10.0), (20.0, 20.0)).to_polygon();
+        let parts = MultiPolygon::new(vec![small, large]);
+        let center: Coord<f64> = parts.visual_center(0.01).unwrap();
+        assert!((center.x - 15.0).abs() < 0.01 && (center.y - 15.0).abs() < 0.01);
+        assert_eq!(MultiPolygon::<f64>::new(vec![]).visual_center(0.01), None);
+    }
+}
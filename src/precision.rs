@@ -0,0 +1,146 @@
+use crate::ViewBox;
+use geo::CoordNum;
+use num_traits::NumCast;
+use std::fmt::{Debug, Display, Formatter, Result};
+
+/// Controls how numbers are written to the SVG output.
+///
+/// Without a precision numbers are written in full, so projected `f64` data can end up with up to
+/// 17 significant digits, and coordinates keep their decimal point, so `210.0` is written as
+/// `210.0`. With a precision trailing zeros are dropped, so it's written as `210`. Numbers too
+/// large or too small to round to the precision in `f64` are written in full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// fixed number of digits after the decimal point
+    Decimals(usize),
+    /// number of significant digits
+    Significant(usize),
+    /// enough decimals to resolve a ten-thousandth of the larger side of the document viewbox
+    Auto,
+}
+
+impl Precision {
+    /// replace [`Precision::Auto`] with the fixed number of decimals it stands for in `viewbox`
+    pub fn resolve(self, viewbox: &ViewBox) -> Self {
+        match self {
+            Precision::Auto => {
+                let extent = viewbox.width().max(viewbox.height());
+                if extent > 0.0 && extent.is_finite() {
+                    Precision::Decimals((4 - extent.log10().floor() as i32).max(0) as usize)
+                } else {
+                    Precision::Decimals(0)
+                }
+            }
+            precision => precision,
+        }
+    }
+
+    /// round `value`, returning it with the number of decimals left to write, or `None` if
+    /// rounding it to this precision overflows `f64`
+    pub(crate) fn round(self, value: f64) -> Option<(f64, usize)> {
+        let decimals = match self {
+            Precision::Decimals(decimals) => decimals.min(i32::MAX as usize) as i32,
+            Precision::Significant(digits) if value != 0.0 => {
+                digits.max(1) as i32 - 1 - value.abs().log10().floor() as i32
+            }
+            Precision::Significant(_) | Precision::Auto => 0,
+        };
+        let scale = 10f64.powi(decimals);
+        let mut scaled = (value * scale).round();
+        if !scale.is_finite() || scale == 0.0 || !scaled.is_finite() {
+            return None;
+        }
+        if decimals < 0 {
+            return Some((scaled / scale, 0));
+        }
+        let mut decimals = decimals;
+        while decimals > 0 && scaled % 10.0 == 0.0 {
+            scaled /= 10.0;
+            decimals -= 1;
+        }
+        Some((scaled / 10f64.powi(decimals), decimals as usize))
+    }
+
+    /// write `value` rounded to this precision
+    fn write(self, fmt: &mut Formatter, value: impl NumCast) -> Result {
+        let value = NumCast::from(value).unwrap_or(f64::NAN);
+        let Some((value, decimals)) = self.round(value) else {
+            return Display::fmt(&value, fmt);
+        };
+        if value == 0.0 {
+            // avoids writing `-0`
+            fmt.write_str("0")
+        } else {
+            write!(fmt, "{:.*}", decimals, value)
+        }
+    }
+}
+
+/// Number written with an optional [`Precision`], falling back to its `Debug` representation.
+pub(crate) struct Number<T>(pub T, pub Option<Precision>);
+
+impl<T: CoordNum> Display for Number<T> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self.1 {
+            Some(precision) => precision.write(fmt, self.0),
+            None => Debug::fmt(&self.0, fmt),
+        }
+    }
+}
+
+/// Number written with an optional [`Precision`], falling back to its `Display` representation.
+pub(crate) struct DisplayNumber<T>(pub T, pub Option<Precision>);
+
+impl<T: CoordNum + Display> Display for DisplayNumber<T> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self.1 {
+            Some(precision) => precision.write(fmt, self.0),
+            None => Display::fmt(&self.0, fmt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(value: f64, precision: Precision) -> String {
+        Number(value, Some(precision)).to_string()
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(format(210.0, Precision::Decimals(2)), "210");
+        assert_eq!(format(1.23456, Precision::Decimals(2)), "1.23");
+        assert_eq!(format(1.2, Precision::Decimals(3)), "1.2");
+        assert_eq!(format(-0.0001, Precision::Decimals(2)), "0");
+    }
+
+    #[test]
+    fn test_significant() {
+        assert_eq!(format(1.23456, Precision::Significant(3)), "1.23");
+        assert_eq!(format(0.00123456, Precision::Significant(2)), "0.0012");
+        assert_eq!(format(4_512_345.678, Precision::Significant(3)), "4510000");
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(format(1.5, Precision::Decimals(400)), "1.5");
+        assert_eq!(format(1e300, Precision::Decimals(10)), format!("{}", 1e300));
+        assert_eq!(format(f64::NAN, Precision::Decimals(2)), "NaN");
+    }
+
+    #[test]
+    fn test_auto() {
+        let viewbox = ViewBox::new(0.0, 0.0, 250.0, 100.0);
+        assert_eq!(Precision::Auto.resolve(&viewbox), Precision::Decimals(2));
+        let viewbox = ViewBox::new(0.0, 0.0, 0.001, 0.001);
+        assert_eq!(Precision::Auto.resolve(&viewbox), Precision::Decimals(7));
+    }
+
+    #[test]
+    fn test_without_precision() {
+        assert_eq!(Number(210.0, None).to_string(), "210.0");
+        assert_eq!(DisplayNumber(210.0, None).to_string(), "210");
+    }
+}
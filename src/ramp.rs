@@ -0,0 +1,372 @@
+use crate::{Color, Style};
+
+/// Continuous range of colors interpolated between stops, e.g. from a [`palette`].
+///
+/// Colors are interpolated in RGB, together with their alpha, see [`Color::mix`].
+///
+/// [`palette`]: crate::palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRamp {
+    /// positions between 0 and 1, in increasing order, and the colors at these positions
+    pub stops: Vec<(f64, Color)>,
+}
+
+impl ColorRamp {
+    /// ramp going through `colors` evenly spaced between 0 and 1
+    pub fn new(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+        Self {
+            stops: colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f64 / last, *color))
+                .collect(),
+        }
+    }
+
+    /// ramp going through colors at explicit positions between 0 and 1
+    pub fn with_stops(mut stops: Vec<(f64, Color)>) -> Self {
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self { stops }
+    }
+
+    /// color at `position`, clamped between 0 and 1, or the color of the first stop if
+    /// `position` is NaN
+    pub fn color_at(&self, position: f64) -> Color {
+        let Some(((first, first_color), (last, last_color))) =
+            self.stops.first().zip(self.stops.last())
+        else {
+            return Color::Named("none");
+        };
+        // there's nothing to interpolate between, even if the stop positions are NaN
+        if position.is_nan() || self.stops.len() == 1 {
+            return *first_color;
+        }
+        let position = position.clamp(0.0, 1.0);
+        if position <= *first {
+            return *first_color;
+        }
+        if position >= *last {
+            return *last_color;
+        }
+        let index = self
+            .stops
+            .iter()
+            .position(|(stop, _)| *stop >= position)
+            .unwrap_or(self.stops.len() - 1);
+        let ((start, start_color), (end, end_color)) = (self.stops[index - 1], self.stops[index]);
+        if position == end {
+            return end_color;
+        }
+        start_color.mix(&end_color, ((position - start) / (end - start)) as f32)
+    }
+
+    /// `count` colors evenly spaced along the ramp, from its start to its end
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        let last = count.saturating_sub(1).max(1) as f64;
+        (0..count).map(|i| self.color_at(i as f64 / last)).collect()
+    }
+}
+
+/// Division of the range of a set of values into classes, each drawn with its own color by a
+/// [`ColorScale::classed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// limits of the classes, in increasing order: class `i` holds the values between
+    /// `bounds[i]` and `bounds[i + 1]`
+    pub bounds: Vec<f64>,
+}
+
+impl Classification {
+    /// `classes` classes of equal width between the minimum and the maximum of `values`
+    pub fn equal_interval(values: &[f64], classes: usize) -> Self {
+        let values = sorted(values);
+        let (Some(min), Some(max)) = (values.first(), values.last()) else {
+            return Self { bounds: vec![] };
+        };
+        let classes = classes.max(1);
+        Self::from_bounds(
+            (0..=classes)
+                .map(|i| min + (max - min) * i as f64 / classes as f64)
+                .collect(),
+        )
+    }
+
+    /// `classes` classes holding the same number of `values`, or fewer if equal values would
+    /// fall in several classes
+    pub fn quantile(values: &[f64], classes: usize) -> Self {
+        let values = sorted(values);
+        let Some(min) = values.first() else {
+            return Self { bounds: vec![] };
+        };
+        let classes = classes.clamp(1, distinct(&values));
+        let mut bounds = vec![*min];
+        bounds.extend((1..=classes).map(|i| values[(i * values.len()).div_ceil(classes) - 1]));
+        Self::from_bounds(bounds)
+    }
+
+    /// `classes` classes minimizing the variance of the `values` within each class, with the
+    /// Jenks natural breaks optimization, or one class per distinct value if there are fewer
+    ///
+    /// This takes a time quadratic in the number of values, so classify a sample of very large
+    /// sets of values.
+    pub fn natural_breaks(values: &[f64], classes: usize) -> Self {
+        let values = sorted(values);
+        let count = values.len();
+        let Some(min) = values.first() else {
+            return Self { bounds: vec![] };
+        };
+        let classes = classes.clamp(1, distinct(&values));
+        // sums of the values and of their squares before each index
+        let mut sums = vec![(0.0, 0.0); count + 1];
+        for (i, value) in values.iter().enumerate() {
+            sums[i + 1] = (sums[i].0 + value, sums[i].1 + value * value);
+        }
+        // sum of squared deviations of the values from `start` to `end` excluded
+        let deviation = |start: usize, end: usize| {
+            let (sum, squares) = (sums[end].0 - sums[start].0, sums[end].1 - sums[start].1);
+            squares - sum * sum / (end - start) as f64
+        };
+        // `costs[end]` is the lowest deviation of the values before `end` split into the
+        // classes processed so far, `starts[class][end]` the start of the last class
+        let mut costs: Vec<f64> = (0..=count)
+            .map(|end| if end == 0 { 0.0 } else { deviation(0, end) })
+            .collect();
+        let mut starts = vec![vec![0; count + 1]];
+        for class in 1..classes {
+            let mut class_costs = vec![f64::INFINITY; count + 1];
+            let mut class_starts = vec![0; count + 1];
+            for end in class + 1..=count {
+                for (start, start_cost) in costs.iter().enumerate().take(end).skip(class) {
+                    let cost = start_cost + deviation(start, end);
+                    if cost < class_costs[end] {
+                        class_costs[end] = cost;
+                        class_starts[end] = start;
+                    }
+                }
+            }
+            costs = class_costs;
+            starts.push(class_starts);
+        }
+        let mut bounds = vec![0.0; classes + 1];
+        bounds[0] = *min;
+        let mut end = count;
+        for class in (0..classes).rev() {
+            bounds[class + 1] = values[end - 1];
+            end = starts[class][end];
+        }
+        Self::from_bounds(bounds)
+    }
+
+    /// classification with the given `bounds`, without the empty classes between equal upper
+    /// bounds; the first class holds its lower bound, so it's kept even if it's also its upper
+    /// bound
+    fn from_bounds(mut bounds: Vec<f64>) -> Self {
+        let mut upper_bounds = bounds.split_off(1.min(bounds.len()));
+        upper_bounds.dedup();
+        bounds.extend(upper_bounds);
+        Self { bounds }
+    }
+
+    /// number of classes
+    pub fn classes(&self) -> usize {
+        self.bounds.len().saturating_sub(1)
+    }
+
+    /// index of the class holding `value`; values out of the bounds go to the first or last
+    /// class
+    pub fn class_of(&self, value: f64) -> usize {
+        let upper_bounds = self.bounds.get(1..).unwrap_or_default();
+        upper_bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(upper_bounds.len().saturating_sub(1))
+    }
+}
+
+/// finite values of `values` in increasing order
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut values: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    values.sort_by(f64::total_cmp);
+    values
+}
+
+/// number of distinct values of the sorted `values`
+fn distinct(values: &[f64]) -> usize {
+    1 + values.windows(2).filter(|pair| pair[0] != pair[1]).count()
+}
+
+/// Mapping of numeric values to colors, e.g. to fill polygons after one of their attributes
+/// with [`ToSvgWith::to_svg_with`]:
+///
+/// ```
+/// # use geo::polygon;
+/// # use geo_svg::{palette, ColorRamp, ColorScale, ToSvgWith};
+/// let parcels = vec![
+///     polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 1.0)],
+///     polygon![(x: 1.0, y: 0.0), (x: 1.0, y: 1.0), (x: 0.0, y: 1.0)],
+/// ];
+/// let population = [150.0, 420.0];
+/// let scale = ColorScale::linear(ColorRamp::new(palette::VIRIDIS), 0.0, 500.0);
+/// let svg = parcels.to_svg_with(|_, index| scale.fill(population[index]));
+/// ```
+///
+/// [`ToSvgWith::to_svg_with`]: crate::ToSvgWith::to_svg_with
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorScale {
+    /// colors interpolated along a ramp, from its start at `min` to its end at `max`
+    Linear { ramp: ColorRamp, min: f64, max: f64 },
+    /// one color per class of a classification
+    Classed {
+        classification: Classification,
+        colors: Vec<Color>,
+    },
+}
+
+impl ColorScale {
+    pub fn linear(ramp: ColorRamp, min: f64, max: f64) -> Self {
+        ColorScale::Linear { ramp, min, max }
+    }
+
+    /// scale with one color per class of `classification`, sampled evenly along `ramp`
+    pub fn classed(classification: Classification, ramp: &ColorRamp) -> Self {
+        let colors = ramp.colors(classification.classes().max(1));
+        ColorScale::Classed {
+            classification,
+            colors,
+        }
+    }
+
+    /// color of `value`
+    pub fn color(&self, value: f64) -> Color {
+        match self {
+            ColorScale::Linear { ramp, min, max } => {
+                let position = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.0
+                };
+                ramp.color_at(position)
+            }
+            ColorScale::Classed {
+                classification,
+                colors,
+            } => colors
+                .get(classification.class_of(value))
+                .or(colors.last())
+                .copied()
+                .unwrap_or(Color::Named("none")),
+        }
+    }
+
+    /// style filling shapes with the color of `value`
+    pub fn fill(&self, value: f64) -> Style {
+        Style {
+            fill: Some(self.color(value).into()),
+            ..Style::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette;
+
+    #[test]
+    fn test_ramp() {
+        let ramp = ColorRamp::new(&[Color::Rgb(0, 0, 0), Color::Hex(0xFF8000)]);
+        assert_eq!(ramp.color_at(-1.0), Color::Rgb(0, 0, 0));
+        assert_eq!(ramp.color_at(0.5), Color::Rgb(128, 64, 0));
+        assert_eq!(ramp.color_at(1.0), Color::Hex(0xFF8000));
+        let ramp = ColorRamp::new(&[Color::Rgba(0, 0, 0, 0.0), Color::Rgb(0, 0, 0)]);
+        assert_eq!(ramp.color_at(0.25), Color::Rgba(0, 0, 0, 0.25));
+        assert_eq!(
+            ColorRamp::new(palette::VIRIDIS).colors(3),
+            vec![
+                Color::Hex(0x440154),
+                Color::Hex(0x21918C),
+                Color::Hex(0xFDE725)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let ramp = ColorRamp::new(&[Color::Rgb(0, 0, 0), Color::Hex(0xFF8000)]);
+        assert_eq!(ramp.color_at(f64::NAN), Color::Rgb(0, 0, 0));
+        assert_eq!(ramp.color_at(f64::INFINITY), Color::Hex(0xFF8000));
+        assert_eq!(ramp.color_at(f64::NEG_INFINITY), Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_single_stop() {
+        let ramp = ColorRamp::new(&[Color::Named("red")]);
+        assert_eq!(ramp.color_at(f64::NAN), Color::Named("red"));
+        assert_eq!(ramp.color_at(0.5), Color::Named("red"));
+        let ramp = ColorRamp::with_stops(vec![(f64::NAN, Color::Named("red"))]);
+        assert_eq!(ramp.color_at(0.5), Color::Named("red"));
+    }
+
+    #[test]
+    fn test_named_stops() {
+        let ramp = ColorRamp::new(&[Color::Named("red"), Color::Named("blue")]);
+        assert_eq!(ramp.color_at(0.5), Color::Rgb(128, 0, 128));
+        let ramp = ColorRamp::new(&[Color::Named("red"), Color::Named("unknown")]);
+        assert_eq!(ramp.color_at(0.4), Color::Named("red"));
+        assert_eq!(ramp.color_at(0.6), Color::Named("unknown"));
+    }
+
+    #[test]
+    fn test_equal_interval() {
+        let classification = Classification::equal_interval(&[4.0, 0.0, 10.0], 4);
+        assert_eq!(classification.bounds, vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(classification.class_of(2.5), 0);
+        assert_eq!(classification.class_of(2.6), 1);
+        assert_eq!(classification.class_of(20.0), 3);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, f64::NAN];
+        let classification = Classification::quantile(&values, 3);
+        assert_eq!(classification.bounds, vec![1.0, 3.0, 5.0, 7.0]);
+    }
+
+    #[test]
+    fn test_natural_breaks() {
+        let values = [1.0, 2.0, 1.5, 10.0, 11.0, 10.5, 30.0, 31.0];
+        let classification = Classification::natural_breaks(&values, 3);
+        assert_eq!(classification.bounds, vec![1.0, 2.0, 11.0, 31.0]);
+        let classification = Classification::natural_breaks(&values, 20);
+        assert_eq!(classification.classes(), values.len());
+        assert_eq!(Classification::natural_breaks(&[], 3).classes(), 0);
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let values = [1.0, 1.0, 1.0, 1.0];
+        for classification in [
+            Classification::equal_interval(&values, 3),
+            Classification::quantile(&values, 3),
+            Classification::natural_breaks(&values, 3),
+        ] {
+            assert_eq!(classification.bounds, vec![1.0, 1.0]);
+            assert_eq!(classification.class_of(1.0), 0);
+        }
+        let values = [1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 3.0];
+        let classification = Classification::quantile(&values, 3);
+        assert_eq!(classification.bounds, vec![1.0, 1.0, 3.0]);
+        assert_eq!(classification.class_of(2.0), 1);
+        let classification = Classification::natural_breaks(&values, 5);
+        assert_eq!(classification.bounds, vec![1.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_classed_scale() {
+        let classification = Classification::equal_interval(&[0.0, 10.0], 2);
+        let scale = ColorScale::classed(classification, &ColorRamp::new(palette::BLUES));
+        assert_eq!(scale.color(1.0), Color::Hex(0xF7FBFF));
+        assert_eq!(scale.color(9.0), Color::Hex(0x08306B));
+    }
+}
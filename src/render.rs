@@ -0,0 +1,34 @@
+use crate::Units;
+
+/// State of the document being rendered, passed to its items next to their [`Style`].
+///
+/// Unlike the style it isn't set through builders or inherited between documents: the root
+/// document fills it in when rendering, and items only read it.
+///
+/// [`Style`]: crate::Style
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderContext {
+    pub(crate) y_up: bool,
+    pub(crate) pixel_size: Option<f64>,
+}
+
+impl RenderContext {
+    /// whether the document flips the y axis, so items can draw text and symbols upright
+    pub fn y_up(&self) -> bool {
+        self.y_up
+    }
+
+    /// size of an output pixel in document units, 1 when the size of the document isn't known
+    /// in absolute units
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size.unwrap_or(1.0)
+    }
+
+    /// `length` given in `units`, in document units
+    pub(crate) fn in_document_units(&self, length: f32, units: Option<Units>) -> f32 {
+        match units {
+            Some(Units::Pixels) => length * self.pixel_size() as f32,
+            Some(Units::Document) | None => length,
+        }
+    }
+}
@@ -1,51 +1,378 @@
-use crate::Color;
+use crate::{svg::Escaped, Marker, Paint, PointSymbol, Precision, RenderContext};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Debug, Clone, PartialEq)]
+/// Presentation of the items of a [`SvgDocument`]. Properties left to `None` are inherited from
+/// the documents it's combined into, or use the renderer defaults.
+///
+/// [`SvgDocument`]: crate::SvgDocument
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Style {
     pub opacity: Option<f32>,
-    pub fill: Option<Color>,
+    pub fill: Option<Paint>,
     pub fill_opacity: Option<f32>,
-    pub stroke_color: Option<Color>,
+    /// fill rule of polygons, rects, triangles and closed line strings; when `None` polygons,
+    /// rects and triangles are filled with evenodd and line strings with the renderer default
+    pub fill_rule: Option<FillRule>,
+    pub stroke_color: Option<Paint>,
     pub stroke_width: Option<f32>,
     pub stroke_opacity: Option<f32>,
-    pub radius: f32,
+    pub stroke_dasharray: Option<Vec<f32>>,
+    pub stroke_dashoffset: Option<f32>,
+    pub stroke_linecap: Option<LineCap>,
+    pub stroke_linejoin: Option<LineJoin>,
+    pub stroke_miterlimit: Option<f32>,
+    /// units of the stroke width, document units when `None`; strokes in pixels keep their
+    /// width however the document is scaled
+    pub stroke_units: Option<Units>,
+    /// marker drawn on the first vertex of lines, line strings and polygons
+    pub marker_start: Option<Marker>,
+    /// marker drawn on the vertices between the first and the last ones
+    pub marker_mid: Option<Marker>,
+    /// marker drawn on the last vertex
+    pub marker_end: Option<Marker>,
+    /// CSS class of the elements
+    pub class: Option<String>,
+    /// radius of points, 1 when `None`
+    pub radius: Option<f32>,
+    /// units of the radius of points, document units when `None`
+    pub radius_units: Option<Units>,
+    /// shape of points, circles when `None`
+    pub point_symbol: Option<PointSymbol>,
+    pub precision: Option<Precision>,
+    /// whether to write compact path data, absolute `M`/`L` commands when `None`
+    pub compact: Option<bool>,
 }
 
-impl Default for Style {
-    fn default() -> Self {
-        Self {
+impl Style {
+    /// this style with the properties it doesn't set taken from `parent`
+    pub fn inherit(&self, parent: &Style) -> Style {
+        Style {
+            opacity: self.opacity.or(parent.opacity),
+            fill: self.fill.clone().or_else(|| parent.fill.clone()),
+            fill_opacity: self.fill_opacity.or(parent.fill_opacity),
+            fill_rule: self.fill_rule.or(parent.fill_rule),
+            stroke_color: self
+                .stroke_color
+                .clone()
+                .or_else(|| parent.stroke_color.clone()),
+            stroke_width: self.stroke_width.or(parent.stroke_width),
+            stroke_opacity: self.stroke_opacity.or(parent.stroke_opacity),
+            stroke_dasharray: self
+                .stroke_dasharray
+                .clone()
+                .or_else(|| parent.stroke_dasharray.clone()),
+            stroke_dashoffset: self.stroke_dashoffset.or(parent.stroke_dashoffset),
+            stroke_linecap: self.stroke_linecap.or(parent.stroke_linecap),
+            stroke_linejoin: self.stroke_linejoin.or(parent.stroke_linejoin),
+            stroke_miterlimit: self.stroke_miterlimit.or(parent.stroke_miterlimit),
+            stroke_units: self.stroke_units.or(parent.stroke_units),
+            marker_start: self.marker_start.or(parent.marker_start),
+            marker_mid: self.marker_mid.or(parent.marker_mid),
+            marker_end: self.marker_end.or(parent.marker_end),
+            class: self.class.clone().or_else(|| parent.class.clone()),
+            radius: self.radius.or(parent.radius),
+            radius_units: self.radius_units.or(parent.radius_units),
+            point_symbol: self
+                .point_symbol
+                .clone()
+                .or_else(|| parent.point_symbol.clone()),
+            precision: self.precision.or(parent.precision),
+            compact: self.compact.or(parent.compact),
+        }
+    }
+
+    /// this style referring to the definitions renamed from the first to the second id of each
+    /// of the `renames`
+    pub(crate) fn with_renamed_ids(&self, renames: &[(String, String)]) -> Style {
+        if renames.is_empty() {
+            return self.clone();
+        }
+        let rename = |id: &String| {
+            renames
+                .iter()
+                .find(|(old, _)| old == id)
+                .map_or_else(|| id.clone(), |(_, new)| new.clone())
+        };
+        let rename_paint = |paint: &Option<Paint>| match paint {
+            Some(Paint::Url(id)) => Some(Paint::Url(rename(id))),
+            paint => paint.clone(),
+        };
+        Style {
+            fill: rename_paint(&self.fill),
+            stroke_color: rename_paint(&self.stroke_color),
+            point_symbol: match &self.point_symbol {
+                Some(PointSymbol::Custom { id, path }) => Some(PointSymbol::Custom {
+                    id: rename(id),
+                    path: path.clone(),
+                }),
+                symbol => symbol.clone(),
+            },
+            ..self.clone()
+        }
+    }
+
+    /// this style without the attributes already in effect in `context`, for elements inside a
+    /// group carrying the `context` attributes
+    pub(crate) fn difference(&self, context: &Style) -> Style {
+        fn unless_equal<T: PartialEq + Clone>(value: &Option<T>, other: &Option<T>) -> Option<T> {
+            if value == other {
+                None
+            } else {
+                value.clone()
+            }
+        }
+        Style {
+            fill: unless_equal(&self.fill, &context.fill),
+            fill_opacity: unless_equal(&self.fill_opacity, &context.fill_opacity),
+            // the shapes would write their default fill rule over the one of the context
+            fill_rule: match self.fill_rule {
+                Some(_) if self.fill_rule == context.fill_rule => Some(FillRule::Unset),
+                fill_rule => fill_rule,
+            },
+            stroke_color: unless_equal(&self.stroke_color, &context.stroke_color),
+            stroke_width: unless_equal(&self.stroke_width, &context.stroke_width),
+            stroke_opacity: unless_equal(&self.stroke_opacity, &context.stroke_opacity),
+            stroke_dasharray: unless_equal(&self.stroke_dasharray, &context.stroke_dasharray),
+            stroke_dashoffset: unless_equal(&self.stroke_dashoffset, &context.stroke_dashoffset),
+            stroke_linecap: unless_equal(&self.stroke_linecap, &context.stroke_linecap),
+            stroke_linejoin: unless_equal(&self.stroke_linejoin, &context.stroke_linejoin),
+            stroke_miterlimit: unless_equal(&self.stroke_miterlimit, &context.stroke_miterlimit),
+            stroke_units: unless_equal(&self.stroke_units, &context.stroke_units),
+            marker_start: unless_equal(&self.marker_start, &context.marker_start),
+            marker_mid: unless_equal(&self.marker_mid, &context.marker_mid),
+            marker_end: unless_equal(&self.marker_end, &context.marker_end),
+            ..self.clone()
+        }
+    }
+
+    /// attributes of this style a `<g>` element can carry for its children; opacity and
+    /// `vector-effect` aren't inherited in SVG and classes aren't inherited at all, so they stay
+    /// on the elements
+    pub(crate) fn group_attributes(&self) -> Style {
+        Style {
             opacity: None,
-            fill: None,
-            fill_opacity: None,
-            stroke_color: None,
-            stroke_width: None,
-            stroke_opacity: None,
-            radius: 1.0,
+            stroke_units: None,
+            class: None,
+            ..self.clone()
+        }
+    }
+
+    /// the presentation attributes of this style, which a stylesheet can carry instead of the
+    /// elements
+    pub(crate) fn presentation(&self) -> Style {
+        Style {
+            opacity: self.opacity,
+            fill: self.fill.clone(),
+            fill_opacity: self.fill_opacity,
+            fill_rule: self
+                .fill_rule
+                .filter(|fill_rule| *fill_rule != FillRule::Unset),
+            stroke_color: self.stroke_color.clone(),
+            stroke_width: self.stroke_width,
+            stroke_opacity: self.stroke_opacity,
+            stroke_dasharray: self.stroke_dasharray.clone(),
+            stroke_dashoffset: self.stroke_dashoffset,
+            stroke_linecap: self.stroke_linecap,
+            stroke_linejoin: self.stroke_linejoin,
+            stroke_miterlimit: self.stroke_miterlimit,
+            stroke_units: self.stroke_units,
+            marker_start: self.marker_start,
+            marker_mid: self.marker_mid,
+            marker_end: self.marker_end,
+            ..Style::default()
+        }
+    }
+
+    /// this style without its presentation attributes, see [`presentation`]
+    ///
+    /// [`presentation`]: Style::presentation
+    pub(crate) fn without_presentation(&self) -> Style {
+        Style {
+            class: self.class.clone(),
+            // the stylesheet overrides the default of the shapes
+            fill_rule: self.fill_rule.map(|_| FillRule::Unset),
+            radius: self.radius,
+            radius_units: self.radius_units,
+            point_symbol: self.point_symbol.clone(),
+            precision: self.precision,
+            compact: self.compact,
+            ..Style::default()
+        }
+    }
+
+    /// call `f` with the name and value of every presentation attribute set in this style
+    fn try_for_each_attribute(&self, mut f: impl FnMut(&str, &dyn Display) -> Result) -> Result {
+        if let Some(opacity) = &self.opacity {
+            f("opacity", opacity)?;
+        }
+        if let Some(fill) = &self.fill {
+            f("fill", fill)?;
+        }
+        if let Some(fill_opacity) = &self.fill_opacity {
+            f("fill-opacity", fill_opacity)?;
+        }
+        if let Some(fill_rule) = self.fill_rule.filter(|rule| *rule != FillRule::Unset) {
+            f("fill-rule", &fill_rule)?;
+        }
+        if let Some(stroke_color) = &self.stroke_color {
+            f("stroke", stroke_color)?;
+        }
+        if let Some(stroke_width) = &self.stroke_width {
+            f("stroke-width", stroke_width)?;
+        }
+        if let Some(stroke_opacity) = &self.stroke_opacity {
+            f("stroke-opacity", stroke_opacity)?;
+        }
+        if let Some(stroke_dasharray) = &self.stroke_dasharray {
+            f("stroke-dasharray", &DashArray(stroke_dasharray))?;
+        }
+        if let Some(stroke_dashoffset) = &self.stroke_dashoffset {
+            f("stroke-dashoffset", stroke_dashoffset)?;
+        }
+        if let Some(stroke_linecap) = &self.stroke_linecap {
+            f("stroke-linecap", stroke_linecap)?;
         }
+        if let Some(stroke_linejoin) = &self.stroke_linejoin {
+            f("stroke-linejoin", stroke_linejoin)?;
+        }
+        if let Some(stroke_miterlimit) = &self.stroke_miterlimit {
+            f("stroke-miterlimit", stroke_miterlimit)?;
+        }
+        if self.stroke_units == Some(Units::Pixels) {
+            f("vector-effect", &"non-scaling-stroke")?;
+        }
+        if let Some(marker_start) = &self.marker_start {
+            f("marker-start", &marker_start.url())?;
+        }
+        if let Some(marker_mid) = &self.marker_mid {
+            f("marker-mid", &marker_mid.url())?;
+        }
+        if let Some(marker_end) = &self.marker_end {
+            f("marker-end", &marker_end.url())?;
+        }
+        Ok(())
+    }
+
+    /// the markers set in this style
+    pub(crate) fn markers(&self) -> impl Iterator<Item = Marker> {
+        [self.marker_start, self.marker_mid, self.marker_end]
+            .into_iter()
+            .flatten()
+    }
+
+    /// radius of points drawn with this style in the `render` context, in document units
+    pub fn point_radius(&self, render: &RenderContext) -> f32 {
+        render.in_document_units(self.radius.unwrap_or(1.0), self.radius_units)
+    }
+
+    /// width of the strokes drawn with this style in the `render` context, in document units
+    pub fn stroke_extent(&self, render: &RenderContext) -> f32 {
+        render.in_document_units(self.stroke_width.unwrap_or(1.0), self.stroke_units)
     }
 }
 
 impl Display for Style {
     fn fmt(&self, fmt: &mut Formatter) -> Result {
-        if let Some(opacity) = self.opacity {
-            write!(fmt, r#" opacity="{}""#, opacity)?;
-        }
-        if let Some(fill) = self.fill {
-            write!(fmt, r#" fill="{}""#, fill)?;
+        if let Some(class) = &self.class {
+            write!(fmt, r#" class="{}""#, Escaped(class))?;
         }
-        if let Some(fill_opacity) = self.fill_opacity {
-            write!(fmt, r#" fill-opacity="{}""#, fill_opacity)?;
+        self.try_for_each_attribute(|name, value| write!(fmt, r#" {name}="{value}""#))
+    }
+}
+
+/// Units of the sizes of a [`Style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    /// the units of the coordinates of the document, scaled along with the geometries
+    Document,
+    /// pixels of the rendered document, estimated from the [`Size`] of the document and 1 per
+    /// document unit without one
+    ///
+    /// [`Size`]: crate::Size
+    Pixels,
+}
+
+/// Presentation attributes of a [`Style`] written as CSS declarations.
+pub(crate) struct Declarations<'a>(pub &'a Style);
+
+impl Display for Declarations<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let mut separator = "";
+        self.0.try_for_each_attribute(|name, value| {
+            write!(fmt, "{separator}{name}:{value}")?;
+            separator = ";";
+            Ok(())
+        })
+    }
+}
+
+struct DashArray<'a>(&'a [f32]);
+
+impl Display for DashArray<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        for (i, dash) in self.0.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            write!(fmt, "{}{}", separator, dash)?;
         }
-        if let Some(stroke_color) = self.stroke_color {
-            write!(fmt, r#" stroke="{}""#, stroke_color)?;
+        Ok(())
+    }
+}
+
+/// Rule deciding which parts of overlapping or nested rings are inside a shape.
+///
+/// It's written as a presentation attribute, so a `fill-rule` set by a CSS stylesheet still
+/// takes precedence over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    EvenOdd,
+    NonZero,
+    /// don't write a fill rule, leaving it to the groups and stylesheets of the document or to
+    /// the renderer (nonzero)
+    Unset,
+}
+
+impl Display for FillRule {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            FillRule::EvenOdd => write!(fmt, "evenodd"),
+            FillRule::NonZero => write!(fmt, "nonzero"),
+            FillRule::Unset => Ok(()),
         }
-        if let Some(stroke_width) = self.stroke_width {
-            write!(fmt, r#" stroke-width="{}""#, stroke_width)?;
+    }
+}
+
+/// Shape at the ends of open strokes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+impl Display for LineCap {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            LineCap::Butt => write!(fmt, "butt"),
+            LineCap::Round => write!(fmt, "round"),
+            LineCap::Square => write!(fmt, "square"),
         }
-        if let Some(stroke_opacity) = self.stroke_opacity {
-            write!(fmt, r#" stroke-opacity="{}""#, stroke_opacity)?;
+    }
+}
+
+/// Shape at the corners of strokes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl Display for LineJoin {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            LineJoin::Miter => write!(fmt, "miter"),
+            LineJoin::Round => write!(fmt, "round"),
+            LineJoin::Bevel => write!(fmt, "bevel"),
         }
-        Ok(())
     }
 }
@@ -0,0 +1,129 @@
+use crate::{style::Declarations, Style};
+use std::fmt::{Display, Formatter, Result, Write};
+
+/// CSS rules written in a `<style>` element at the start of a document, styling the groups and
+/// elements with the given classes.
+///
+/// Only the presentation attributes of the rule styles are written, i.e. colors, opacities and
+/// stroke properties. Combine it with [`SvgDocument::with_class`] to style whole layers from a
+/// single rule:
+///
+/// ```
+/// # use geo::Point;
+/// # use geo_svg::{Color, Style, Stylesheet, ToSvg};
+/// let wells = Point::new(0.0, 0.0);
+/// let svg = wells
+///     .to_svg()
+///     .with_class("wells")
+///     .with_stylesheet(Stylesheet::new().with_rule(
+///         "wells",
+///         &Style {
+///             fill: Some(Color::Named("blue").into()),
+///             ..Style::default()
+///         },
+///     ));
+/// assert_eq!(
+///     svg.svg_str(),
+///     r#"<style>.wells{fill:blue}</style><g class="wells"><circle cx="0.0" cy="0.0" r="1"/></g>"#
+/// );
+/// ```
+///
+/// [`SvgDocument::with_class`]: crate::SvgDocument::with_class
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stylesheet {
+    /// class names and the styles of their elements, in the order they are written
+    pub rules: Vec<(String, Style)>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// style the groups and elements with the CSS class `class` with `style`
+    pub fn with_rule(mut self, class: impl Into<String>, style: &Style) -> Self {
+        self.rules.push((class.into(), style.clone()));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// add a generated class for the presentation attributes of `style`, unless one already
+    /// exists
+    pub(crate) fn add_class_for(&mut self, style: &Style) {
+        let presentation = style.presentation();
+        if presentation != Style::default() && self.class_index(&presentation).is_none() {
+            let class = format!("s{}", self.rules.len());
+            self.rules.push((class, presentation));
+        }
+    }
+
+    /// `style` with its presentation attributes replaced by the generated class added with
+    /// [`add_class_for`]
+    ///
+    /// [`add_class_for`]: Stylesheet::add_class_for
+    pub(crate) fn apply_class(&self, style: &Style) -> Style {
+        let Some(index) = self.class_index(&style.presentation()) else {
+            return style.clone();
+        };
+        let generated = &self.rules[index].0;
+        Style {
+            class: Some(match &style.class {
+                Some(class) => format!("{class} {generated}"),
+                None => generated.clone(),
+            }),
+            ..style.without_presentation()
+        }
+    }
+
+    /// rename the generated classes named like one of the `taken` classes of the document
+    pub(crate) fn rename_clashes(&mut self, taken: &[&str]) {
+        // generated names below the number of rules are already in use
+        let mut next = self.rules.len();
+        for (class, _) in &mut self.rules {
+            while taken.contains(&class.as_str()) {
+                *class = format!("s{next}");
+                next += 1;
+            }
+        }
+    }
+
+    fn class_index(&self, presentation: &Style) -> Option<usize> {
+        self.rules
+            .iter()
+            .position(|(_, style)| style == presentation)
+    }
+}
+
+impl Display for Stylesheet {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        for (class, style) in &self.rules {
+            write!(fmt, ".{}{{{}}}", CssIdentifier(class), Declarations(style))?;
+        }
+        Ok(())
+    }
+}
+
+/// Class name escaped to be written as a CSS identifier. Escaped characters are written as
+/// hexadecimal code points, so the result is also safe to write as XML text.
+struct CssIdentifier<'a>(&'a str);
+
+impl Display for CssIdentifier<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let mut previous = None;
+        for (i, c) in self.0.chars().enumerate() {
+            // identifiers can't start with a digit, or with a hyphen followed by a digit
+            let leading_digit = c.is_ascii_digit() && (i == 0 || (i == 1 && previous == Some('-')));
+            let valid = c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii();
+            if valid && !leading_digit {
+                fmt.write_char(c)?;
+            } else {
+                write!(fmt, "\\{:x} ", c as u32)?;
+            }
+            previous = Some(c);
+        }
+        Ok(())
+    }
+}
@@ -1,133 +1,1308 @@
-use crate::{Color, Style, ToSvgStr, ViewBox};
-use std::fmt::{Display, Formatter, Result};
+use crate::{
+    precision::DisplayNumber, Color, Definition, FillRule, Gradient, LineCap, LineJoin, Marker,
+    Pattern, PointSymbol, Precision, PreserveAspectRatio, RenderContext, Size, Style, Stylesheet,
+    Symbol, ToSvgStr, Units, ViewBox,
+};
+use std::fmt::{Display, Formatter, Result, Write};
+use std::io::{self, Write as _};
+use std::ops::Deref;
+use std::sync::Arc;
 
+/// SVG document borrowing the items it renders, as returned by [`ToSvg::to_svg`].
+///
+/// [`ToSvg::to_svg`]: crate::ToSvg::to_svg
+pub type Svg<'a> = SvgDocument<&'a dyn ToSvgStr>;
+
+/// SVG document owning the items it renders. Unlike [`Svg`] it can be returned from functions,
+/// stored in structs and sent across threads.
+///
+/// Build it with [`IntoOwnedSvg`] to keep its items live. Converting a borrowed [`Svg`] with
+/// `From` renders its items once instead, see [`OwnedSvg::from`].
+///
+/// [`IntoOwnedSvg`]: crate::IntoOwnedSvg
+pub type OwnedSvg = SvgDocument<Arc<dyn ToSvgStr + Send + Sync>>;
+
+/// Tree of items sharing a style, together with the siblings combined through [`and`].
+///
+/// Styles cascade down the tree: the builders of a document only fill in the properties its
+/// siblings didn't set themselves, so `a.with_fill_color(red).and(b).with_fill_color(green)`
+/// keeps `a` red. Use [`with_style_override`] to force a style onto all siblings instead.
+///
+/// The item handle `I` decides whether the document borrows ([`Svg`]) or owns ([`OwnedSvg`])
+/// its items; both share the same builder API and output.
+///
+/// [`and`]: SvgDocument::and
+/// [`with_style_override`]: SvgDocument::with_style_override
 #[derive(Clone)]
-pub struct Svg<'a> {
-    pub items: Vec<&'a dyn ToSvgStr>,
-    pub siblings: Vec<Svg<'a>>,
+pub struct SvgDocument<I> {
+    pub items: Vec<I>,
+    pub siblings: Vec<SvgDocument<I>>,
     pub viewbox: ViewBox,
     pub style: Style,
+    pub size: Option<Size>,
+    pub preserve_aspect_ratio: PreserveAspectRatio,
+    pub y_up: bool,
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    /// render as a `<g>` element carrying the style shared by the items and siblings, implied by
+    /// an `id` or `classes`
+    pub group: bool,
+    pub stylesheet: Stylesheet,
+    /// replace the presentation attributes of the elements with classes defined in the
+    /// stylesheet
+    pub style_classes: bool,
+    /// paint servers and other elements referenced by the elements of this document and its
+    /// siblings, written once in the `<defs>` of the whole document; a definition sharing its id
+    /// with a different one is written with a new id, which this document refers to instead
+    pub definitions: Vec<Definition>,
+    /// derive the default stroke width and point radius from the extent of the document
+    pub auto_sizes: bool,
 }
 
-impl<'a> Svg<'a> {
-    pub fn and(mut self, sibling: Svg<'a>) -> Self {
+impl<I> Default for SvgDocument<I> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            siblings: vec![],
+            viewbox: ViewBox::default(),
+            style: Style::default(),
+            size: None,
+            preserve_aspect_ratio: PreserveAspectRatio::default(),
+            y_up: false,
+            id: None,
+            classes: vec![],
+            group: false,
+            stylesheet: Stylesheet::default(),
+            style_classes: false,
+            definitions: vec![],
+            auto_sizes: false,
+        }
+    }
+}
+
+impl<I> SvgDocument<I>
+where
+    I: Deref + Clone,
+    I::Target: ToSvgStr,
+{
+    pub fn and(mut self, sibling: SvgDocument<I>) -> Self {
+        if !self.items.is_empty() || self.style != Style::default() || self.renders_group() {
+            // group both documents so the style and group of this one don't apply to `sibling`
+            self = SvgDocument {
+                size: self.size.take(),
+                preserve_aspect_ratio: self.preserve_aspect_ratio,
+                y_up: self.y_up,
+                stylesheet: std::mem::take(&mut self.stylesheet),
+                style_classes: self.style_classes,
+                auto_sizes: self.auto_sizes,
+                siblings: vec![self],
+                ..Default::default()
+            };
+        }
         self.siblings.push(sibling);
         self
     }
 
     pub fn with_style(mut self, style: &Style) -> Self {
         self.style = style.clone();
+        self
+    }
+
+    /// force the properties set in `style` onto this document and all its siblings, overriding
+    /// the ones they set themselves
+    pub fn with_style_override(mut self, style: &Style) -> Self {
+        self.style = style.inherit(&self.style);
         for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_style(style);
+            *sibling = sibling.clone().with_style_override(style);
         }
         self
     }
 
-    pub fn with_margin(mut self, margin: f32) -> Self {
+    /// render this document as a `<g>` element, see [`with_group`]
+    ///
+    /// [`with_group`]: SvgDocument::with_group
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// add a CSS class to this document and render it as a `<g>` element, see [`with_group`]
+    ///
+    /// [`with_group`]: SvgDocument::with_group
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+
+    /// render this document as a `<g>` element carrying the style attributes shared by its items
+    /// and siblings instead of repeating them on every element, which makes the output smaller
+    /// and lets editors toggle it as a layer
+    pub fn with_group(mut self, group: bool) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// write the rules of `stylesheet` in a `<style>` element at the start of the document,
+    /// along with the rules of the stylesheets of the documents combined with this one
+    pub fn with_stylesheet(mut self, stylesheet: Stylesheet) -> Self {
+        self.stylesheet = stylesheet;
+        self
+    }
+
+    /// write each distinct set of presentation attributes once, as a generated class of the
+    /// stylesheet, and refer to it by class name from the elements, which makes the output of
+    /// many items sharing a few styles much smaller
+    pub fn with_style_classes(mut self, style_classes: bool) -> Self {
+        self.style_classes = style_classes;
+        self
+    }
+
+    /// define `gradient` in the document, so styles can refer to it with [`Gradient::paint`]
+    pub fn with_gradient(mut self, gradient: Gradient) -> Self {
+        self.definitions.push(Definition::Gradient(gradient));
+        self
+    }
+
+    /// define `pattern` in the document, so styles can refer to it with [`Pattern::paint`]
+    pub fn with_pattern(mut self, pattern: Pattern) -> Self {
+        self.definitions.push(Definition::Pattern(pattern));
+        self
+    }
+
+    pub fn with_margin(mut self, margin: f64) -> Self {
         self.viewbox = self.viewbox.with_margin(margin);
         self
     }
 
+    /// set the `width` and `height` of the root element, which otherwise are left to the
+    /// renderer
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_preserve_aspect_ratio(
+        mut self,
+        preserve_aspect_ratio: PreserveAspectRatio,
+    ) -> Self {
+        self.preserve_aspect_ratio = preserve_aspect_ratio;
+        self
+    }
+
     pub fn with_color(mut self, color: Color) -> Self {
-        self.style.fill = Some(color);
-        self.style.stroke_color = Some(color);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_color(color);
-        }
+        self.style.fill = Some(color.into());
+        self.style.stroke_color = Some(color.into());
         self
     }
 
     pub fn with_opacity(mut self, opacity: f32) -> Self {
         self.style.opacity = Some(opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_opacity(opacity);
-        }
         self
     }
 
     pub fn with_fill_color(mut self, fill: Color) -> Self {
-        self.style.fill = Some(fill);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_fill_color(fill);
-        }
+        self.style.fill = Some(fill.into());
         self
     }
 
+    /// define `gradient` in the document and fill with it
+    pub fn with_fill_gradient(mut self, gradient: Gradient) -> Self {
+        self.style.fill = Some(gradient.paint());
+        self.with_gradient(gradient)
+    }
+
+    /// define `pattern` in the document and fill with it
+    pub fn with_fill_pattern(mut self, pattern: Pattern) -> Self {
+        self.style.fill = Some(pattern.paint());
+        self.with_pattern(pattern)
+    }
+
     pub fn with_fill_opacity(mut self, fill_opacity: f32) -> Self {
         self.style.fill_opacity = Some(fill_opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_fill_opacity(fill_opacity);
-        }
+        self
+    }
+
+    /// set the fill rule of closed shapes, see [`FillRule`]
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.style.fill_rule = Some(fill_rule);
         self
     }
 
     pub fn with_stroke_width(mut self, stroke_width: f32) -> Self {
         self.style.stroke_width = Some(stroke_width);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_width(stroke_width);
-        }
         self
     }
 
     pub fn with_stroke_opacity(mut self, stroke_opacity: f32) -> Self {
         self.style.stroke_opacity = Some(stroke_opacity);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_opacity(stroke_opacity);
-        }
+        self
+    }
+
+    /// set the units of the stroke width, see [`Units`]
+    pub fn with_stroke_units(mut self, units: Units) -> Self {
+        self.style.stroke_units = Some(units);
         self
     }
 
     pub fn with_stroke_color(mut self, stroke_color: Color) -> Self {
-        self.style.stroke_color = Some(stroke_color);
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_stroke_color(stroke_color);
-        }
+        self.style.stroke_color = Some(stroke_color.into());
+        self
+    }
+
+    /// define `gradient` in the document and stroke with it
+    pub fn with_stroke_gradient(mut self, gradient: Gradient) -> Self {
+        self.style.stroke_color = Some(gradient.paint());
+        self.with_gradient(gradient)
+    }
+
+    /// draw strokes as dashes, alternating the lengths of dashes and gaps in `dasharray`
+    pub fn with_stroke_dasharray(mut self, dasharray: Vec<f32>) -> Self {
+        self.style.stroke_dasharray = Some(dasharray);
+        self
+    }
+
+    pub fn with_stroke_dashoffset(mut self, dashoffset: f32) -> Self {
+        self.style.stroke_dashoffset = Some(dashoffset);
+        self
+    }
+
+    pub fn with_stroke_linecap(mut self, linecap: LineCap) -> Self {
+        self.style.stroke_linecap = Some(linecap);
+        self
+    }
+
+    pub fn with_stroke_linejoin(mut self, linejoin: LineJoin) -> Self {
+        self.style.stroke_linejoin = Some(linejoin);
+        self
+    }
+
+    /// draw `marker` on the first vertex of lines, line strings and polygons
+    pub fn with_marker_start(mut self, marker: Marker) -> Self {
+        self.style.marker_start = Some(marker);
+        self
+    }
+
+    /// draw `marker` on the vertices between the first and the last ones
+    pub fn with_marker_mid(mut self, marker: Marker) -> Self {
+        self.style.marker_mid = Some(marker);
+        self
+    }
+
+    /// draw `marker` on the last vertex, e.g. [`Marker::arrow`] to show directions
+    pub fn with_marker_end(mut self, marker: Marker) -> Self {
+        self.style.marker_end = Some(marker);
+        self
+    }
+
+    pub fn with_stroke_miterlimit(mut self, miterlimit: f32) -> Self {
+        self.style.stroke_miterlimit = Some(miterlimit);
         self
     }
 
     pub fn with_radius(mut self, radius: f32) -> Self {
-        self.style.radius = radius;
-        for sibling in &mut self.siblings {
-            *sibling = sibling.clone().with_radius(radius);
-        }
+        self.style.radius = Some(radius);
+        self
+    }
+
+    /// set the units of the radius of points, see [`Units`]
+    pub fn with_radius_units(mut self, units: Units) -> Self {
+        self.style.radius_units = Some(units);
+        self
+    }
+
+    /// draw points as `symbol` instead of circles
+    pub fn with_point_symbol(mut self, symbol: PointSymbol) -> Self {
+        self.style.point_symbol = Some(symbol);
+        self
+    }
+
+    /// set the precision of the numbers written to the output, see [`Precision`]
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.style.precision = Some(precision);
+        self
+    }
+
+    /// write paths without redundant letters and whitespace, which makes large outputs
+    /// substantially smaller; with [`Precision::Decimals`] paths also use relative commands
+    pub fn with_compact_paths(mut self, compact: bool) -> Self {
+        self.style.compact = Some(compact);
+        self
+    }
+
+    /// make the y axis point up, as on maps and plots, instead of down; text stays readable
+    pub fn with_y_up(mut self) -> Self {
+        self.y_up = true;
+        self
+    }
+
+    /// draw strokes and points without a width or radius of their own with sizes proportional
+    /// to the extent of the document, so geometries of any scale render legibly
+    pub fn with_auto_sizes(mut self) -> Self {
+        self.auto_sizes = true;
         self
     }
 
     pub fn svg_str(&self) -> String {
-        self.items
+        let mut svg_str = String::new();
+        self.write_svg_str(&mut svg_str)
+            .expect("writing to a String can't fail");
+        svg_str
+    }
+
+    /// write the elements of this document and its siblings to `writer`, without the enclosing
+    /// `<svg>` element
+    pub fn write_svg_str(&self, writer: &mut dyn Write) -> Result {
+        self.write_content(writer, &self.viewbox())
+    }
+
+    /// write the stylesheet and the elements of this document showing `viewbox`
+    fn write_content(&self, writer: &mut dyn Write, viewbox: &ViewBox) -> Result {
+        let pass = self.pass(viewbox);
+        let mut stylesheet = Stylesheet::default();
+        self.collect_stylesheets(&mut stylesheet);
+        if !stylesheet.is_empty() || pass.classes.is_some() {
+            writer.write_str("<style>")?;
+            write!(writer, "{stylesheet}")?;
+            if let Some(generated) = &pass.classes {
+                write!(writer, "{generated}")?;
+            }
+            writer.write_str("</style>")?;
+        }
+        if !pass.definitions.written.is_empty() {
+            writer.write_str("<defs>")?;
+            for definition in &pass.definitions.written {
+                writer.write_str(&definition.markup)?;
+            }
+            writer.write_str("</defs>")?;
+        }
+        self.write_elements(writer, &pass.root_style, &pass.context(), &pass)
+    }
+
+    /// collect the definitions and generated classes of the elements of this document showing
+    /// `viewbox`
+    fn pass<'p>(&self, viewbox: &'p ViewBox) -> Pass<'p, '_, I> {
+        let mut pass = Pass {
+            root_style: self.root_style(viewbox),
+            render: self.render_context(viewbox),
+            viewbox,
+            classes: None,
+            definitions: Definitions::default(),
+        };
+        self.collect_definitions(
+            &mut pass.definitions,
+            &[],
+            &pass.root_style,
+            &pass.render,
+            viewbox,
+        );
+        if self.style_classes {
+            let mut generated = Stylesheet::default();
+            self.collect_classes(
+                &mut generated,
+                &pass.root_style,
+                &pass.context(),
+                &pass.definitions,
+                viewbox,
+            );
+            let mut taken = vec![];
+            self.collect_user_classes(&mut taken);
+            generated.rename_clashes(&taken);
+            pass.classes = (!generated.is_empty()).then_some(generated);
+        }
+        pass
+    }
+
+    /// write the elements of this document with the `parent` style, inside groups already
+    /// carrying the attributes of the `context` style
+    fn write_elements(
+        &self,
+        writer: &mut dyn Write,
+        parent: &Style,
+        context: &Style,
+        pass: &Pass<'_, '_, I>,
+    ) -> Result {
+        let styles = self.element_styles(parent, context, pass);
+        if let Some(group_style) = &styles.group {
+            writer.write_str("<g")?;
+            if let Some(id) = &self.id {
+                write!(writer, r#" id="{}""#, Escaped(id))?;
+            }
+            write!(writer, "{group_style}>")?;
+        }
+        for item in &self.items {
+            item.write_svg(writer, &styles.items, &pass.render)?;
+        }
+        for sibling in &self.siblings {
+            sibling.write_elements(writer, &styles.resolved, &styles.context, pass)?;
+        }
+        if self.renders_group() {
+            writer.write_str("</g>")?;
+        }
+        Ok(())
+    }
+
+    /// styles of the elements of this document with the `parent` style, inside groups already
+    /// carrying the attributes of the `context` style
+    fn element_styles(
+        &self,
+        parent: &Style,
+        context: &Style,
+        pass: &Pass<'_, '_, I>,
+    ) -> ElementStyles {
+        let apply_classes = |style: Style| match &pass.classes {
+            Some(classes) => classes.apply_class(&style),
+            None => style,
+        };
+        let renames = pass.definitions.renames_in(self);
+        let resolved = self.resolved_style(parent, pass.viewbox, renames);
+        let group = self.group_style(&resolved, context);
+        let context = match group {
+            Some(_) => resolved.group_attributes(),
+            None => context.clone(),
+        };
+        ElementStyles {
+            group: group.map(apply_classes),
+            items: apply_classes(resolved.difference(&context)),
+            resolved,
+            context,
+        }
+    }
+
+    /// add the classes generated for the elements of this document to `classes`, following
+    /// [`write_elements`]
+    ///
+    /// [`write_elements`]: SvgDocument::write_elements
+    fn collect_classes(
+        &self,
+        classes: &mut Stylesheet,
+        parent: &Style,
+        context: &Style,
+        definitions: &Definitions<'_, I>,
+        viewbox: &ViewBox,
+    ) {
+        let style = self.resolved_style(parent, viewbox, definitions.renames_in(self));
+        let group_context;
+        let context = if let Some(group_style) = self.group_style(&style, context) {
+            classes.add_class_for(&group_style);
+            group_context = style.group_attributes();
+            &group_context
+        } else {
+            context
+        };
+        if !self.items.is_empty() {
+            classes.add_class_for(&style.difference(context));
+        }
+        for sibling in &self.siblings {
+            sibling.collect_classes(classes, &style, context, definitions, viewbox);
+        }
+    }
+
+    /// add the rules of the stylesheets of this document and its siblings to `stylesheet`,
+    /// skipping the ones already in it
+    fn collect_stylesheets(&self, stylesheet: &mut Stylesheet) {
+        for rule in &self.stylesheet.rules {
+            if !stylesheet.rules.contains(rule) {
+                stylesheet.rules.push(rule.clone());
+            }
+        }
+        for sibling in &self.siblings {
+            sibling.collect_stylesheets(stylesheet);
+        }
+    }
+
+    /// add the class names given by the user to this document and its siblings to `classes`,
+    /// which generated classes must not take
+    fn collect_user_classes<'a>(&'a self, classes: &mut Vec<&'a str>) {
+        let style_classes = self
+            .style
+            .class
+            .iter()
+            .flat_map(|class| class.split_whitespace());
+        let rule_classes = self
+            .stylesheet
+            .rules
             .iter()
-            .map(|item| item.to_svg_str(&self.style))
-            .chain(self.siblings.iter().map(Svg::svg_str))
-            .collect()
+            .map(|(class, _)| class.as_str());
+        classes.extend(
+            self.classes
+                .iter()
+                .flat_map(|class| class.split_whitespace())
+                .chain(style_classes)
+                .chain(rule_classes),
+        );
+        for sibling in &self.siblings {
+            sibling.collect_user_classes(classes);
+        }
+    }
+
+    /// add the definitions of this document and its siblings to `definitions`, along with the
+    /// markers and point symbols of their styles, with the `inherited` renames of the documents
+    /// they're combined into in effect
+    fn collect_definitions<'a>(
+        &'a self,
+        definitions: &mut Definitions<'a, I>,
+        inherited: &[(String, String)],
+        parent: &Style,
+        render: &RenderContext,
+        viewbox: &ViewBox,
+    ) {
+        let mut renames = inherited.to_vec();
+        let precision = self.resolved_style(parent, viewbox, &renames).precision;
+        for definition in &self.definitions {
+            definitions.add(definition, precision, &mut renames);
+        }
+        for marker in self.style.markers() {
+            definitions.add(&Definition::Marker(marker), None, &mut renames);
+        }
+        let style = self.resolved_style(parent, viewbox, &renames);
+        if let Some(symbol) = Symbol::for_style(&style, render).filter(|_| !self.items.is_empty()) {
+            definitions.add(&Definition::Symbol(symbol), None, &mut renames);
+        }
+        if !renames.is_empty() {
+            definitions.renames.push((self, renames.clone()));
+        }
+        let style = self.resolved_style(parent, viewbox, &renames);
+        for sibling in &self.siblings {
+            sibling.collect_definitions(definitions, &renames, &style, render, viewbox);
+        }
+    }
+
+    /// style of the `<g>` element of this document with the resolved `style`, inside groups
+    /// carrying the attributes of the `context` style, if it renders one
+    fn group_style(&self, style: &Style, context: &Style) -> Option<Style> {
+        if !self.renders_group() {
+            return None;
+        }
+        let classes = (!self.classes.is_empty()).then(|| self.classes.join(" "));
+        Some(Style {
+            class: classes,
+            ..style.group_attributes().difference(context)
+        })
+    }
+
+    fn renders_group(&self) -> bool {
+        self.group || self.id.is_some() || !self.classes.is_empty()
+    }
+
+    /// stream the whole document to `writer`, e.g. a file or a socket, without building it in
+    /// memory first; the output is buffered, so `writer` doesn't need to be
+    pub fn write_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        write!(writer, "{}", self)?;
+        writer.flush()
     }
 
     pub fn viewbox(&self) -> ViewBox {
+        if !self.auto_sizes && (self.size.is_none() || !self.uses_pixel_units()) {
+            // sizes don't depend on the viewbox
+            let viewbox = ViewBox::default();
+            return self.viewbox_with(&self.root_style(&viewbox), &self.render_context(&viewbox));
+        }
+        // sizes in pixels and auto sizes depend on the viewbox: estimate them from the viewbox
+        // of the geometries alone, then refine them once
+        let geometries = self.viewbox_with(
+            &Style {
+                stroke_width: self.auto_sizes.then_some(0.0),
+                radius: self.auto_sizes.then_some(0.0),
+                ..self.root_style(&ViewBox::default())
+            },
+            &RenderContext {
+                pixel_size: Some(0.0),
+                ..self.render_context(&ViewBox::default())
+            },
+        );
+        let estimate = self.viewbox_with(
+            &self.root_style(&geometries),
+            &self.render_context(&geometries),
+        );
+        self.viewbox_with(&self.root_style(&estimate), &self.render_context(&estimate))
+    }
+
+    /// whether the style of this document or of its siblings has sizes in pixels
+    fn uses_pixel_units(&self) -> bool {
+        let pixels = Some(Units::Pixels);
+        self.style.radius_units == pixels
+            || self.style.stroke_units == pixels
+            || self.siblings.iter().any(SvgDocument::uses_pixel_units)
+    }
+
+    /// viewbox of this document when combined into a document with the `parent` style and
+    /// rendered in the `render` context
+    pub(crate) fn viewbox_with(&self, parent: &Style, render: &RenderContext) -> ViewBox {
+        let style = self.style.inherit(parent);
         self.items
             .iter()
-            .map(|item| item.viewbox(&self.style))
-            .chain(self.siblings.iter().map(Svg::viewbox))
+            .map(|item| item.viewbox(&style, render))
+            .chain(
+                self.siblings
+                    .iter()
+                    .map(|sibling| sibling.viewbox_with(&style, render)),
+            )
             .fold(self.viewbox, |viewbox, other_viewbox| {
                 viewbox.add(&other_viewbox)
             })
     }
+
+    /// style the root document showing `viewbox` passes on to its siblings
+    fn root_style(&self, viewbox: &ViewBox) -> Style {
+        let extent = viewbox.width().max(viewbox.height());
+        let auto_size = |fraction: f64| {
+            (self.auto_sizes && extent > 0.0 && extent.is_finite()).then(|| {
+                let size = extent * fraction;
+                Precision::Significant(2)
+                    .round(size)
+                    .map_or(size, |(size, _)| size) as f32
+            })
+        };
+        Style {
+            stroke_width: auto_size(0.002),
+            radius: auto_size(0.005),
+            ..Style::default()
+        }
+    }
+
+    /// state of the root document showing `viewbox` its items are rendered in
+    fn render_context(&self, viewbox: &ViewBox) -> RenderContext {
+        RenderContext {
+            y_up: self.y_up,
+            pixel_size: self.pixel_size(viewbox),
+        }
+    }
+
+    /// size of an output pixel in document units when showing `viewbox`, if the size of the
+    /// document is known in absolute units
+    fn pixel_size(&self, viewbox: &ViewBox) -> Option<f64> {
+        let (width, height) = self.size?.resolve(viewbox);
+        let scale_x = width.pixels()? / viewbox.width();
+        let scale_y = height.pixels()? / viewbox.height();
+        let scale = match self.preserve_aspect_ratio {
+            PreserveAspectRatio::Slice(..) => scale_x.max(scale_y),
+            PreserveAspectRatio::Meet(..) | PreserveAspectRatio::None => scale_x.min(scale_y),
+        };
+        (scale.is_finite() && scale > 0.0).then(|| 1.0 / scale)
+    }
+
+    /// style the items of this document are rendered with, inside a document with the `parent`
+    /// style showing `viewbox`, referring to the definitions given new ids by `renames`
+    fn resolved_style(
+        &self,
+        parent: &Style,
+        viewbox: &ViewBox,
+        renames: &[(String, String)],
+    ) -> Style {
+        // the parent style already refers to the new ids
+        let mut style = self.style.with_renamed_ids(renames).inherit(parent);
+        style.precision = style.precision.map(|precision| precision.resolve(viewbox));
+        style
+    }
 }
 
-impl<'a> Display for Svg<'a> {
+impl OwnedSvg {
+    /// create a document owning `item`
+    pub fn new(item: impl ToSvgStr + Send + Sync + 'static) -> Self {
+        Self {
+            items: vec![Arc::new(item)],
+            ..Default::default()
+        }
+    }
+
+    /// owned copy of `svg` whose items are rendered once with the styles of the elements of
+    /// `svg` in `pass`, inside a document with the `parent` style and groups carrying the
+    /// attributes of the `context` style
+    fn snapshot(
+        svg: &Svg,
+        parent: &Style,
+        context: &Style,
+        pass: &Pass<'_, '_, &dyn ToSvgStr>,
+    ) -> Self {
+        let styles = svg.element_styles(parent, context, pass);
+        Self {
+            items: svg
+                .items
+                .iter()
+                .map(|item| {
+                    Arc::new(Snapshot {
+                        svg_str: item.to_svg_str(&styles.items, &pass.render),
+                        viewbox: item.viewbox(&styles.resolved, &pass.render),
+                    }) as Arc<dyn ToSvgStr + Send + Sync>
+                })
+                .collect(),
+            siblings: svg
+                .siblings
+                .iter()
+                .map(|sibling| OwnedSvg::snapshot(sibling, &styles.resolved, &styles.context, pass))
+                .collect(),
+            viewbox: svg.viewbox,
+            style: svg.style.clone(),
+            size: svg.size,
+            preserve_aspect_ratio: svg.preserve_aspect_ratio,
+            y_up: svg.y_up,
+            id: svg.id.clone(),
+            classes: svg.classes.clone(),
+            group: svg.group,
+            stylesheet: svg.stylesheet.clone(),
+            style_classes: svg.style_classes,
+            definitions: svg.definitions.clone(),
+            auto_sizes: svg.auto_sizes,
+        }
+    }
+}
+
+/// Definitions written once in the `<defs>` of a document, collected from its whole tree.
+///
+/// Definitions are told apart by their markup: equal ones are written once, and a definition
+/// whose id is taken by a different one is given a new id, which the documents defining it and
+/// their siblings refer to instead.
+struct Definitions<'a, I> {
+    written: Vec<WrittenDefinition>,
+    renames: Vec<(&'a SvgDocument<I>, Renames)>,
+}
+
+/// ids of the definitions renamed in a document and its siblings, as `(id, new id)` pairs
+type Renames = Vec<(String, String)>;
+
+struct WrittenDefinition {
+    /// id the definition was given by its document
+    original_id: String,
+    /// markup of the definition with its original id
+    original: String,
+    id: String,
+    markup: String,
+}
+
+impl<I> Default for Definitions<'_, I> {
+    fn default() -> Self {
+        Self {
+            written: vec![],
+            renames: vec![],
+        }
+    }
+}
+
+impl<'a, I> Definitions<'a, I> {
+    /// add `definition` written with `precision`, recording its new id in `renames` if its id
+    /// is taken
+    fn add(
+        &mut self,
+        definition: &Definition,
+        precision: Option<Precision>,
+        renames: &mut Renames,
+    ) {
+        let original_id = definition.id().into_owned();
+        let original = markup(definition, precision);
+        let id = if let Some(written) = self
+            .written
+            .iter()
+            .find(|written| written.original_id == original_id && written.original == original)
+        {
+            written.id.clone()
+        } else if self.is_taken(&original_id) {
+            let mut n = 2;
+            let id = loop {
+                let id = format!("{original_id}-{n}");
+                if !self.is_taken(&id) {
+                    break id;
+                }
+                n += 1;
+            };
+            // markers and point symbols are named after their content, so definitions sharing
+            // their id only differ in rounding
+            let Some(renamed) = definition.with_id(id.clone()) else {
+                return;
+            };
+            let markup = markup(&renamed, precision);
+            self.push(original_id.clone(), original, id.clone(), markup);
+            id
+        } else {
+            self.push(
+                original_id.clone(),
+                original.clone(),
+                original_id.clone(),
+                original,
+            );
+            original_id.clone()
+        };
+        renames.retain(|(old, _)| *old != original_id);
+        if id != original_id {
+            renames.push((original_id, id));
+        }
+    }
+
+    fn push(&mut self, original_id: String, original: String, id: String, markup: String) {
+        self.written.push(WrittenDefinition {
+            original_id,
+            original,
+            id,
+            markup,
+        });
+    }
+
+    fn is_taken(&self, id: &str) -> bool {
+        self.written.iter().any(|written| written.id == id)
+    }
+
+    /// ids of the definitions renamed in `document`, as `(id, new id)`
+    fn renames_in(&self, document: &SvgDocument<I>) -> &[(String, String)] {
+        self.renames
+            .iter()
+            .find(|(renamed, _)| std::ptr::eq(*renamed, document))
+            .map_or(&[], |(_, renames)| renames)
+    }
+}
+
+fn markup(definition: &Definition, precision: Option<Precision>) -> String {
+    let mut markup = String::new();
+    definition
+        .write(&mut markup, precision)
+        .expect("writing to a String can't fail");
+    markup
+}
+
+/// State of the pass writing the elements of a document, shared by the whole tree.
+struct Pass<'p, 'a, I> {
+    /// style the root document passes on to its siblings
+    root_style: Style,
+    render: RenderContext,
+    viewbox: &'p ViewBox,
+    /// generated classes to refer to instead of writing presentation attributes
+    classes: Option<Stylesheet>,
+    definitions: Definitions<'a, I>,
+}
+
+impl<I> Pass<'_, '_, I> {
+    /// attributes in effect outside of all groups
+    fn context(&self) -> Style {
+        // no element carries the auto stroke width yet
+        Style {
+            stroke_width: None,
+            ..self.root_style.clone()
+        }
+    }
+}
+
+/// Styles of the elements of a document in a [`Pass`].
+struct ElementStyles {
+    /// style of the document, passed on to its siblings
+    resolved: Style,
+    /// style of its `<g>` element, if it renders one
+    group: Option<Style>,
+    /// attributes in effect for its items and siblings
+    context: Style,
+    /// style its items are written with
+    items: Style,
+}
+
+/// String escaped to be written as XML text or attribute value.
+pub(crate) struct Escaped<'a>(pub &'a str);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => fmt.write_str("&amp;")?,
+                '<' => fmt.write_str("&lt;")?,
+                '>' => fmt.write_str("&gt;")?,
+                '"' => fmt.write_str("&quot;")?,
+                c => fmt.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Item of an [`OwnedSvg`] converted from a borrowed [`Svg`]. The borrowed item can't be
+/// cloned through its trait object, so its markup and viewbox are captured with the style it's
+/// written with at conversion time.
+struct Snapshot {
+    svg_str: String,
+    viewbox: ViewBox,
+}
+
+impl ToSvgStr for Snapshot {
+    fn write_svg(&self, writer: &mut dyn Write, _style: &Style, _render: &RenderContext) -> Result {
+        writer.write_str(&self.svg_str)
+    }
+
+    fn viewbox(&self, _style: &Style, _render: &RenderContext) -> ViewBox {
+        self.viewbox
+    }
+}
+
+impl<'a> From<Svg<'a>> for OwnedSvg {
+    /// Frozen render of `svg`: its items are rendered once, with the styles, classes and
+    /// definition ids they're written with in `svg`, since the borrowed items can't be moved
+    /// into the owned document. The result writes the same SVG as `svg`.
+    ///
+    /// The document structure and its groups stay live, but builders called on the result
+    /// afterwards don't change the elements of the converted items, e.g. a fill color set
+    /// afterwards doesn't fill them:
+    ///
+    /// ```
+    /// # use geo::Point;
+    /// # use geo_svg::{Color, OwnedSvg, ToSvg};
+    /// let well = Point::new(10.0, 20.0);
+    /// let frozen = OwnedSvg::from(well.to_svg().with_radius(2.0));
+    /// assert_eq!(
+    ///     frozen.with_fill_color(Color::RED).svg_str(),
+    ///     r#"<circle cx="10.0" cy="20.0" r="2"/>"#
+    /// );
+    /// ```
+    fn from(svg: Svg<'a>) -> Self {
+        let viewbox = svg.viewbox();
+        let pass = svg.pass(&viewbox);
+        OwnedSvg::snapshot(&svg, &pass.root_style, &pass.context(), &pass)
+    }
+}
+
+impl<I> Display for SvgDocument<I>
+where
+    I: Deref + Clone,
+    I::Target: ToSvgStr,
+{
     fn fmt(&self, fmt: &mut Formatter) -> Result {
         let viewbox = self.viewbox();
+        let precision = self
+            .style
+            .precision
+            .map(|precision| precision.resolve(&viewbox));
+        // the viewbox is the sum of the shapes and their margins, so without a precision it
+        // carries float noise of its own
+        let viewbox_precision = precision.unwrap_or_else(|| Precision::Auto.resolve(&viewbox));
+        let shown_viewbox = if self.y_up { viewbox.flip_y() } else { viewbox };
+        write!(fmt, r#"<svg xmlns="http://www.w3.org/2000/svg""#)?;
+        if let Some(size) = self.size {
+            let (width, height) = size.resolve(&viewbox);
+            write!(fmt, r#" width="{width}" height="{height}""#)?;
+        }
         write!(
             fmt,
-            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="{x} {y} {w} {h}">{content}</svg>"#,
-            x = viewbox.min_x(),
-            y = viewbox.min_y(),
-            w = viewbox.width(),
-            h = viewbox.height(),
-            content = self
-                .items
-                .iter()
-                .map(|item| item.to_svg_str(&self.style))
-                .chain(self.siblings.iter().map(Svg::svg_str))
-                .collect::<String>()
-        )
+            r#" preserveAspectRatio="{preserve_aspect_ratio}" viewBox="{x} {y} {w} {h}">"#,
+            preserve_aspect_ratio = self.preserve_aspect_ratio,
+            x = DisplayNumber(shown_viewbox.min_x(), Some(viewbox_precision)),
+            y = DisplayNumber(shown_viewbox.min_y(), Some(viewbox_precision)),
+            w = DisplayNumber(shown_viewbox.width(), Some(viewbox_precision)),
+            h = DisplayNumber(shown_viewbox.height(), Some(viewbox_precision)),
+        )?;
+        if self.y_up {
+            fmt.write_str(r#"<g transform="scale(1,-1)">"#)?;
+        }
+        self.write_content(fmt, &viewbox)?;
+        if self.y_up {
+            fmt.write_str("</g>")?;
+        }
+        fmt.write_str("</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Align, IntoOwnedSvg, Length, Text, ToSvg};
+    use geo::{Coord, Line, Point};
+
+    fn layer() -> OwnedSvg {
+        Point::new(10.0, 28.1).into_owned_svg().with_radius(2.0)
+    }
+
+    #[test]
+    fn test_owned_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+        assert_send_sync(&layer());
+    }
+
+    #[test]
+    fn test_owned_matches_borrowed() {
+        let point = Point::new(10.0, 28.1);
+        let line = Line::new((114.19, 22.26), (15.93, -15.76));
+        let borrowed = point
+            .to_svg()
+            .with_radius(2.0)
+            .and(line.to_svg().with_stroke_width(2.5))
+            .with_fill_color(Color::Named("red"));
+        let owned = layer()
+            .and(line.into_owned_svg().with_stroke_width(2.5))
+            .with_fill_color(Color::Named("red"));
+        assert_eq!(owned.to_string(), borrowed.to_string());
+        assert_eq!(
+            OwnedSvg::from(borrowed.clone()).to_string(),
+            borrowed.to_string()
+        );
+    }
+
+    #[test]
+    fn test_write_to() {
+        let svg = layer().with_fill_color(Color::Named("red"));
+        let mut buffer = Vec::new();
+        svg.write_to(&mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), svg.to_string());
+    }
+
+    #[test]
+    fn test_y_up() {
+        let point = Point::new(10.0, 20.0);
+        let label = Text::new("a", Coord { x: 10.0, y: 20.0 });
+        let svg = point.to_svg().and(label.to_svg()).with_y_up();
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="8 -22 4 4"><g transform="scale(1,-1)"><circle cx="10.0" cy="20.0" r="1"/><text font-size="10" x="10" y="-20" transform="scale(1,-1)">a</text></g></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_size() {
+        let svg = layer()
+            .with_size(Size::Width(Length::Mm(50.0)))
+            .with_preserve_aspect_ratio(PreserveAspectRatio::Meet(Align::Min, Align::Min));
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="50mm" height="50mm" preserveAspectRatio="xMinYMin meet" viewBox="7 25.1 6 6"><circle cx="10.0" cy="28.1" r="2"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_cascade() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        let svg = a
+            .to_svg()
+            .with_fill_color(Color::Named("red"))
+            .and(b.to_svg())
+            .with_fill_color(Color::Named("green"))
+            .with_radius(2.0);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<circle cx="0.0" cy="0.0" r="2" fill="red"/><circle cx="10.0" cy="0.0" r="2" fill="green"/>"#
+        );
+        let svg = svg.with_style_override(&Style {
+            fill: Some(Color::Named("blue").into()),
+            ..Style::default()
+        });
+        assert_eq!(
+            svg.svg_str(),
+            r#"<circle cx="0.0" cy="0.0" r="2" fill="blue"/><circle cx="10.0" cy="0.0" r="2" fill="blue"/>"#
+        );
+    }
+
+    #[test]
+    fn test_group() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        let svg = a
+            .to_svg()
+            .with_fill_color(Color::Named("red"))
+            .and(b.to_svg())
+            .with_id("wells")
+            .with_class("layer")
+            .with_class("points")
+            .with_fill_color(Color::Named("green"))
+            .with_stroke_color(Color::Named("black"))
+            .with_opacity(0.5);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<g id="wells" class="layer points" fill="green" stroke="black"><circle cx="0.0" cy="0.0" r="1" opacity="0.5" fill="red"/><circle cx="10.0" cy="0.0" r="1" opacity="0.5"/></g>"#
+        );
+        assert_eq!(OwnedSvg::from(svg.clone()).to_string(), svg.to_string());
+    }
+
+    #[test]
+    fn test_style_classes() {
+        let roads = [
+            Line::new((0.0, 0.0), (10.0, 0.0)),
+            Line::new((0.0, 5.0), (10.0, 5.0)),
+        ];
+        let well = Point::new(5.0, 2.0);
+        let svg = roads[0]
+            .to_svg()
+            .and(roads[1].to_svg())
+            .with_class("roads")
+            .with_stroke_color(Color::Named("black"))
+            .and(well.to_svg().with_fill_color(Color::Named("blue")))
+            .with_stroke_width(2.0)
+            .with_stylesheet(Stylesheet::new().with_rule(
+                "roads",
+                &Style {
+                    stroke_linecap: Some(LineCap::Round),
+                    ..Style::default()
+                },
+            ))
+            .with_style_classes(true);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<style>.roads{stroke-linecap:round}.s0{stroke:black;stroke-width:2}.s1{fill:blue;stroke-width:2}</style><g class="roads s0"><path d="M 0.0 0.0 L 10.0 0.0"/><path d="M 0.0 5.0 L 10.0 5.0"/></g><circle cx="5.0" cy="2.0" r="1" class="s1"/>"#
+        );
+        assert_eq!(OwnedSvg::from(svg.clone()).to_string(), svg.to_string());
+    }
+
+    #[test]
+    fn test_style_class_names() {
+        let well = Point::new(5.0, 2.0);
+        let svg = well
+            .to_svg()
+            .with_class("s0")
+            .with_fill_color(Color::Named("blue"))
+            .with_stylesheet(Stylesheet::new().with_rule(
+                "1st<a&b>",
+                &Style {
+                    stroke_linecap: Some(LineCap::Round),
+                    ..Style::default()
+                },
+            ))
+            .with_style_classes(true);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<style>.\31 st\3c a\26 b\3e {stroke-linecap:round}.s1{fill:blue}</style><g class="s0 s1"><circle cx="5.0" cy="2.0" r="1"/></g>"#
+        );
+        assert_eq!(OwnedSvg::from(svg.clone()).to_string(), svg.to_string());
+    }
+
+    #[test]
+    fn test_sibling_stylesheets() {
+        let rule = |class: &str, color| {
+            Stylesheet::new().with_rule(
+                class,
+                &Style {
+                    fill: Some(Color::Named(color).into()),
+                    ..Style::default()
+                },
+            )
+        };
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        let svg = a
+            .to_svg()
+            .with_class("wells")
+            .with_stylesheet(rule("wells", "blue"))
+            .and(b.to_svg().with_class("springs").with_stylesheet(
+                rule("springs", "green").with_rule(
+                    "wells",
+                    &Style {
+                        fill: Some(Color::Named("blue").into()),
+                        ..Style::default()
+                    },
+                ),
+            ));
+        assert_eq!(
+            svg.svg_str(),
+            r#"<style>.wells{fill:blue}.springs{fill:green}</style><g class="wells"><circle cx="0.0" cy="0.0" r="1"/></g><g class="springs"><circle cx="10.0" cy="0.0" r="1"/></g>"#
+        );
+    }
+
+    #[test]
+    fn test_definitions() {
+        let heat = Gradient::linear("heat", (0.0, 0.0), (1.0, 0.0))
+            .with_stop(0.0, Color::YELLOW)
+            .with_stop(1.0, Color::RED);
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        let svg = a
+            .to_svg()
+            .with_fill_gradient(heat.clone())
+            .and(b.to_svg().with_stroke_gradient(heat));
+        assert_eq!(
+            svg.svg_str(),
+            r#"<defs><linearGradient id="heat" gradientUnits="objectBoundingBox" x1="0" y1="0" x2="1" y2="0"><stop offset="0" stop-color="yellow"/><stop offset="1" stop-color="red"/></linearGradient></defs><circle cx="0.0" cy="0.0" r="1" fill="url(#heat)"/><circle cx="10.0" cy="0.0" r="1" stroke="url(#heat)"/>"#
+        );
+    }
+
+    #[test]
+    fn test_conflicting_definitions() {
+        let heat = |color| Gradient::linear("heat", (0.0, 0.0), (1.0, 0.0)).with_stop(0.0, color);
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(10.0, 0.0);
+        let outlined = Style {
+            stroke_color: Some(heat(Color::BLUE).paint()),
+            ..Style::default()
+        };
+        let svg = a
+            .to_svg()
+            .with_fill_gradient(heat(Color::RED))
+            .and(
+                b.to_svg()
+                    .and(a.to_svg().with_style(&outlined))
+                    .with_fill_gradient(heat(Color::BLUE)),
+            )
+            .and(b.to_svg().with_stroke_gradient(heat(Color::RED)));
+        assert_eq!(
+            svg.svg_str(),
+            concat!(
+                r#"<defs><linearGradient id="heat" gradientUnits="objectBoundingBox" x1="0" y1="0" x2="1" y2="0"><stop offset="0" stop-color="red"/></linearGradient>"#,
+                r#"<linearGradient id="heat-2" gradientUnits="objectBoundingBox" x1="0" y1="0" x2="1" y2="0"><stop offset="0" stop-color="blue"/></linearGradient></defs>"#,
+                r#"<circle cx="0.0" cy="0.0" r="1" fill="url(#heat)"/><circle cx="10.0" cy="0.0" r="1" fill="url(#heat-2)"/>"#,
+                r#"<circle cx="0.0" cy="0.0" r="1" fill="url(#heat-2)" stroke="url(#heat-2)"/><circle cx="10.0" cy="0.0" r="1" stroke="url(#heat)"/>"#,
+            )
+        );
+        assert_eq!(OwnedSvg::from(svg.clone()).to_string(), svg.to_string());
+    }
+
+    #[test]
+    fn test_definition_precision() {
+        let a = Point::new(0.0, 0.0);
+        let svg = a
+            .to_svg()
+            .with_fill_pattern(Pattern::dots("dots").with_spacing(10.0 / 3.0))
+            .with_precision(Precision::Decimals(2));
+        assert_eq!(
+            svg.svg_str(),
+            r#"<defs><pattern id="dots" patternUnits="userSpaceOnUse" width="3.33" height="3.33"><circle cx="1.67" cy="1.67" r="0.5" fill="black"/></pattern></defs><circle cx="0" cy="0" r="1" fill="url(#dots)"/>"#
+        );
+    }
+
+    #[test]
+    fn test_markers() {
+        let a = Line::new((0.0, 0.0), (10.0, 0.0));
+        let b = Line::new((0.0, 5.0), (10.0, 5.0));
+        let svg = a
+            .to_svg()
+            .and(b.to_svg().with_marker_start(Marker::bar()))
+            .with_marker_end(Marker::arrow())
+            .with_marker_mid(Marker::arrow());
+        assert_eq!(
+            svg.svg_str(),
+            concat!(
+                r#"<defs><marker id="marker-arrow-3-stroke" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="3" markerHeight="3" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 Z" fill="context-stroke"/></marker>"#,
+                r#"<marker id="marker-bar-3-stroke" viewBox="0 0 10 10" refX="5" refY="5" markerWidth="3" markerHeight="3" orient="auto-start-reverse"><rect x="4" width="2" height="10" fill="context-stroke"/></marker></defs>"#,
+                r#"<path d="M 0.0 0.0 L 10.0 0.0" marker-mid="url(#marker-arrow-3-stroke)" marker-end="url(#marker-arrow-3-stroke)"/>"#,
+                r#"<path d="M 0.0 5.0 L 10.0 5.0" marker-start="url(#marker-bar-3-stroke)" marker-mid="url(#marker-arrow-3-stroke)" marker-end="url(#marker-arrow-3-stroke)"/>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_pixel_units() {
+        let a = Point::new(0.0, 0.0);
+        let b = Line::new((0.0, 0.0), (100.0, 50.0));
+        let svg = a
+            .to_svg()
+            .with_radius(4.0)
+            .with_radius_units(Units::Pixels)
+            .and(b.to_svg())
+            .with_stroke_width(2.0)
+            .with_stroke_units(Units::Pixels)
+            .with_size(Size::Width(Length::Px(200.0)));
+        // 200 pixels for about 104 units: the point is padded by 6 pixels and the line by 2; the
+        // height is rounded down to 103.99 pixels, so the vertical scale sets the pixel size
+        let viewbox = svg.viewbox();
+        assert!((viewbox.min_x() + 3.12).abs() < 1e-6);
+        assert!((viewbox.max_x() - 101.04).abs() < 1e-6);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<circle cx="0.0" cy="0.0" r="2.0832772" stroke-width="2" vector-effect="non-scaling-stroke"/><path d="M 0.0 0.0 L 100.0 50.0" stroke-width="2" vector-effect="non-scaling-stroke"/>"#
+        );
+    }
+
+    #[test]
+    fn test_auto_sizes() {
+        let a = Point::new(0.0, 0.0);
+        let b = Line::new((0.0, 0.0), (1000.0, 500.0));
+        let svg = a
+            .to_svg()
+            .and(b.to_svg().with_stroke_width(4.0))
+            .with_auto_sizes();
+        assert_eq!(
+            svg.svg_str(),
+            r#"<circle cx="0.0" cy="0.0" r="5.1" stroke-width="2"/><path d="M 0.0 0.0 L 1000.0 500.0" stroke-width="4"/>"#
+        );
+        let viewbox = svg.viewbox();
+        assert!(viewbox.min_x() < -7.0 && viewbox.max_x() > 1003.0);
     }
 }
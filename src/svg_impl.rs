@@ -1,143 +1,144 @@
-use crate::{Style, ToSvgStr, ViewBox};
+use crate::{
+    path::PathData,
+    precision::{DisplayNumber, Number},
+    svg::Escaped,
+    FillRule, RenderContext, Style, Symbol, ToSvgStr, ViewBox,
+};
 use geo::{
     Coord, CoordNum, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
     MultiPolygon, Point, Polygon, Rect, Triangle,
 };
 use num_traits::NumCast;
+use std::fmt::{Result, Write};
+
+/// write the start of a `<path>` element up to its `d` attribute, with the `default` fill rule
+/// of the shape unless `style` sets one
+fn write_path_start(writer: &mut dyn Write, style: &Style, default: Option<FillRule>) -> Result {
+    writer.write_str("<path")?;
+    if let (None, Some(default)) = (style.fill_rule, default) {
+        write!(writer, r#" fill-rule="{default}""#)?;
+    }
+    writer.write_str(r#" d=""#)
+}
+
+/// viewbox of the square of half side `padding` centered on `coord`
+fn padded_viewbox<T: CoordNum>(coord: Coord<T>, padding: f64) -> ViewBox {
+    let x: f64 = NumCast::from(coord.x).unwrap_or(0.0);
+    let y: f64 = NumCast::from(coord.y).unwrap_or(0.0);
+    ViewBox::new(x - padding, y - padding, x + padding, y + padding)
+}
 
 impl<T: CoordNum> ToSvgStr for Coord<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Point::from(*self).to_svg_str(style)
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        Point::from(*self).write_svg(writer, style, render)
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        Point::from(*self).viewbox(style)
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        Point::from(*self).viewbox(style, render)
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Point<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        format!(
-            r#"<circle cx="{x:?}" cy="{y:?}" r="{radius}"{style}/>"#,
-            x = self.x(),
-            y = self.y(),
-            radius = style.radius,
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        if let Some(symbol) = Symbol::for_style(style, render) {
+            return write!(
+                writer,
+                r##"<use href="#{id}" x="{x}" y="{y}"{style}/>"##,
+                id = Escaped(&symbol.id()),
+                x = Number(self.x(), style.precision),
+                y = Number(self.y(), style.precision),
+            );
+        }
+        write!(
+            writer,
+            r#"<circle cx="{x}" cy="{y}" r="{radius}"{style}/>"#,
+            x = Number(self.x(), style.precision),
+            y = Number(self.y(), style.precision),
+            radius = DisplayNumber(style.point_radius(render), style.precision),
             style = style,
         )
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        let radius = style.radius + style.stroke_width.unwrap_or(1.0);
-        ViewBox::new(
-            NumCast::from(self.x()).unwrap_or(0f32) - radius,
-            NumCast::from(self.y()).unwrap_or(0f32) - radius,
-            NumCast::from(self.x()).unwrap_or(0f32) + radius,
-            NumCast::from(self.y()).unwrap_or(0f32) + radius,
-        )
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        let radius = (style.point_radius(render) + style.stroke_extent(render)) as f64;
+        padded_viewbox(self.0, radius)
     }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiPoint<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        self.0.iter().map(|point| point.to_svg_str(style)).collect()
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        self.0
+            .iter()
+            .try_for_each(|point| point.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.0.iter().fold(ViewBox::default(), |view_box, point| {
-            view_box.add(&point.viewbox(style))
+            view_box.add(&point.viewbox(style, render))
         })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Line<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        format!(
-            r#"<path d="M {x1:?} {y1:?} L {x2:?} {y2:?}"{style}/>"#,
-            x1 = self.start.x,
-            y1 = self.start.y,
-            x2 = self.end.x,
-            y2 = self.end.y,
-            style = style,
-        )
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, _render: &RenderContext) -> Result {
+        write_path_start(writer, style, None)?;
+        PathData::new(writer, style).ring(&[self.start, self.end], false)?;
+        write!(writer, r#""{style}/>"#)
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        let style = Style {
-            radius: 0.0,
-            ..style.clone()
-        };
-        self.start.viewbox(&style).add(&self.end.viewbox(&style))
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        // unlike points, the ends of lines only reach as far as their stroke
+        let padding = style.stroke_extent(render) as f64;
+        padded_viewbox(self.start, padding).add(&padded_viewbox(self.end, padding))
     }
 }
 
 impl<T: CoordNum> ToSvgStr for LineString<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, _render: &RenderContext) -> Result {
         let len = self.0.len();
         if len < 2 {
-            return String::default();
+            return Ok(());
         }
         let delta = if self.is_closed() { 1 } else { 0 };
-        let path_string = self
-            .0
-            .iter()
-            .take(len.saturating_sub(delta))
-            .map(|p| format!("{x:?} {y:?}", x = p.x, y = p.y))
-            .enumerate()
-            .fold(String::from("M"), |s, (i, p)| {
-                let sep = if i == 0 { " " } else { " L " };
-                s + sep + p.as_str()
-            });
-        let end = if self.is_closed() { " Z" } else { "" };
-        format!(r#"<path d="{path}"{style}/>"#, path = path_string + end)
-    }
-
-    fn viewbox(&self, style: &Style) -> ViewBox {
+        write_path_start(writer, style, None)?;
+        PathData::new(writer, style).ring(&self.0[..len - delta], self.is_closed())?;
+        write!(writer, r#""{style}/>"#)
+    }
+
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.lines().fold(ViewBox::default(), |view_box, line| {
-            view_box.add(&line.viewbox(style))
+            view_box.add(&line.viewbox(style, render))
         })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiLineString<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         self.0
             .iter()
-            .map(|line_string| line_string.to_svg_str(style))
-            .collect()
+            .try_for_each(|line_string| line_string.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.0
             .iter()
             .fold(ViewBox::default(), |view_box, line_string| {
-                view_box.add(&line_string.viewbox(style))
+                view_box.add(&line_string.viewbox(style, render))
             })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Polygon<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        use std::fmt::Write;
-        let mut path = String::new();
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, _render: &RenderContext) -> Result {
+        write_path_start(writer, style, Some(FillRule::EvenOdd))?;
+        let mut path = PathData::new(writer, style);
         for contour in std::iter::once(self.exterior()).chain(self.interiors().iter()) {
-            let mut points = contour.points();
-            if let Some(first_point) = points.next() {
-                write!(path, "M {:?} {:?}", first_point.x(), first_point.y()).unwrap()
-            }
-            for point in points {
-                write!(path, " L {:?} {:?}", point.x(), point.y()).unwrap();
-            }
-            write!(path, " Z ").unwrap();
+            path.ring(&contour.0, true)?;
         }
-
-        format!(
-            r#"<path fill-rule="evenodd" d="{path}"{style}/>"#,
-            path = path,
-            style = style,
-        )
+        write!(writer, r#""{style}/>"#)
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.exterior()
             .lines()
             .chain(
@@ -146,131 +147,132 @@ impl<T: CoordNum> ToSvgStr for Polygon<T> {
                     .flat_map(|interior| interior.lines()),
             )
             .fold(ViewBox::default(), |view_box, line_string| {
-                view_box.add(&line_string.viewbox(style))
+                view_box.add(&line_string.viewbox(style, render))
             })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Rect<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Polygon::from(*self).to_svg_str(style)
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        Polygon::from(*self).write_svg(writer, style, render)
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        Polygon::from(*self).viewbox(style)
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        Polygon::from(*self).viewbox(style, render)
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Triangle<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        Polygon::new(self.to_array().iter().cloned().collect(), vec![]).to_svg_str(style)
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
+        Polygon::new(self.to_array().iter().cloned().collect(), vec![])
+            .write_svg(writer, style, render)
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        Polygon::new(self.to_array().iter().cloned().collect(), vec![]).viewbox(style)
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        Polygon::new(self.to_array().iter().cloned().collect(), vec![]).viewbox(style, render)
     }
 }
 
 impl<T: CoordNum> ToSvgStr for MultiPolygon<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         self.0
             .iter()
-            .map(|polygons| polygons.to_svg_str(style))
-            .collect()
+            .try_for_each(|polygons| polygons.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.0
             .iter()
             .fold(ViewBox::default(), |view_box, polygons| {
-                view_box.add(&polygons.viewbox(style))
+                view_box.add(&polygons.viewbox(style, render))
             })
     }
 }
 
 impl<T: CoordNum> ToSvgStr for Geometry<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         use Geometry::*;
         match self {
-            Point(point) => point.to_svg_str(style),
-            Line(line) => line.to_svg_str(style),
-            LineString(line_tring) => line_tring.to_svg_str(style),
-            Triangle(triangle) => triangle.to_polygon().to_svg_str(style),
-            Rect(rect) => rect.to_polygon().to_svg_str(style),
-            Polygon(polygon) => polygon.to_svg_str(style),
-            MultiPoint(multi_point) => multi_point.to_svg_str(style),
-            MultiLineString(multi_line_string) => multi_line_string.to_svg_str(style),
-            MultiPolygon(multi_polygon) => multi_polygon.to_svg_str(style),
-            GeometryCollection(geometry_collection) => geometry_collection.to_svg_str(style),
+            Point(point) => point.write_svg(writer, style, render),
+            Line(line) => line.write_svg(writer, style, render),
+            LineString(line_tring) => line_tring.write_svg(writer, style, render),
+            Triangle(triangle) => triangle.to_polygon().write_svg(writer, style, render),
+            Rect(rect) => rect.to_polygon().write_svg(writer, style, render),
+            Polygon(polygon) => polygon.write_svg(writer, style, render),
+            MultiPoint(multi_point) => multi_point.write_svg(writer, style, render),
+            MultiLineString(multi_line_string) => {
+                multi_line_string.write_svg(writer, style, render)
+            }
+            MultiPolygon(multi_polygon) => multi_polygon.write_svg(writer, style, render),
+            GeometryCollection(geometry_collection) => {
+                geometry_collection.write_svg(writer, style, render)
+            }
         }
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         use Geometry::*;
         match self {
-            Point(point) => point.viewbox(style),
-            Line(line) => line.viewbox(style),
-            LineString(line_tring) => line_tring.viewbox(style),
-            Triangle(triangle) => triangle.to_polygon().viewbox(style),
-            Rect(rect) => rect.to_polygon().viewbox(style),
-            Polygon(polygon) => polygon.viewbox(style),
-            MultiPoint(multi_point) => multi_point.viewbox(style),
-            MultiLineString(multi_line_string) => multi_line_string.viewbox(style),
-            MultiPolygon(multi_polygon) => multi_polygon.viewbox(style),
-            GeometryCollection(geometry_collection) => geometry_collection.viewbox(style),
+            Point(point) => point.viewbox(style, render),
+            Line(line) => line.viewbox(style, render),
+            LineString(line_tring) => line_tring.viewbox(style, render),
+            Triangle(triangle) => triangle.to_polygon().viewbox(style, render),
+            Rect(rect) => rect.to_polygon().viewbox(style, render),
+            Polygon(polygon) => polygon.viewbox(style, render),
+            MultiPoint(multi_point) => multi_point.viewbox(style, render),
+            MultiLineString(multi_line_string) => multi_line_string.viewbox(style, render),
+            MultiPolygon(multi_polygon) => multi_polygon.viewbox(style, render),
+            GeometryCollection(geometry_collection) => geometry_collection.viewbox(style, render),
         }
     }
 }
 
 impl<T: CoordNum> ToSvgStr for GeometryCollection<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         self.0
             .iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.0
             .iter()
             .fold(ViewBox::default(), |view_box, geometry| {
-                view_box.add(&geometry.viewbox(style))
+                view_box.add(&geometry.viewbox(style, render))
             })
     }
 }
 
-impl<'a, T: ToSvgStr> ToSvgStr for &'a [T] {
-    fn to_svg_str(&self, style: &Style) -> String {
+impl<T: ToSvgStr> ToSvgStr for &[T] {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         self.iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.iter().fold(ViewBox::default(), |view_box, item| {
-            view_box.add(&item.viewbox(style))
+            view_box.add(&item.viewbox(style, render))
         })
     }
 }
 
 impl<T: ToSvgStr> ToSvgStr for Vec<T> {
-    fn to_svg_str(&self, style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         self.iter()
-            .map(|geometry| geometry.to_svg_str(style))
-            .collect()
+            .try_for_each(|geometry| geometry.write_svg(writer, style, render))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
         self.iter().fold(ViewBox::default(), |view_box, item| {
-            view_box.add(&item.viewbox(style))
+            view_box.add(&item.viewbox(style, render))
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Color, ToSvg};
-    use geo::{LineString, Point, Polygon};
+    use crate::{Color, FillRule, LineCap, LineJoin, Precision, ToSvg};
+    use geo::{Line, LineString, Point, Polygon, Triangle};
 
     #[test]
     fn test_point() {
@@ -354,4 +356,159 @@ mod tests {
             r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="209 -1 92 92"><path d="M 210.0 0.0 L 300.0 0.0 L 300.0 90.0 L 210.0 90.0" fill="black" stroke="red"/></svg>"#
         )
     }
+
+    #[test]
+    fn test_precision() {
+        let line_string_result = LineString(vec![
+            (210.123456, 0.0).into(),
+            (300.0, 0.0).into(),
+            (300.0, 90.987654).into(),
+        ])
+        .to_svg()
+        .with_stroke_color(Color::Named("red"))
+        .with_precision(Precision::Decimals(2))
+        .to_string();
+        assert_eq!(
+            line_string_result,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="209.12 -1 91.88 92.99"><path d="M 210.12 0 L 300 0 L 300 90.99" stroke="red"/></svg>"#
+        )
+    }
+
+    #[test]
+    fn test_compact_polygon() {
+        let polygon = Polygon::new(
+            LineString(vec![
+                (210.0, 0.0).into(),
+                (300.0, 0.0).into(),
+                (300.0, 90.0).into(),
+                (210.0, 90.0).into(),
+            ]),
+            vec![LineString(vec![
+                (230.0, 20.0).into(),
+                (280.0, 20.0).into(),
+                (250.0, 70.5).into(),
+            ])],
+        );
+        let polygon_result = polygon
+            .to_svg()
+            .with_compact_paths(true)
+            .with_precision(Precision::Decimals(1))
+            .to_string();
+        assert_eq!(
+            polygon_result,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="209 -1 92 92"><path fill-rule="evenodd" d="M210 0h90v90h-90zm20 20h50l-30 50.5z"/></svg>"#
+        );
+        assert_eq!(
+            polygon.to_svg().with_compact_paths(true).svg_str(),
+            r#"<path fill-rule="evenodd" d="M210 0H300V90H210zM230 20H280L250 70.5z"/>"#
+        );
+    }
+
+    #[test]
+    fn test_compact_significant() {
+        let line_string = LineString::from(vec![
+            (0.5, 0.0),
+            (1234.4, 0.0),
+            (2468.3, 0.0),
+            (3702.2, 0.0),
+        ]);
+        assert_eq!(
+            line_string
+                .to_svg()
+                .with_compact_paths(true)
+                .with_precision(Precision::Significant(3))
+                .svg_str(),
+            r#"<path d="M0.5 0H1230 2470 3700"/>"#
+        );
+        let line = Line::new((0.1, 0.1), (0.3, 0.2));
+        assert_eq!(
+            line.to_svg().with_compact_paths(true).svg_str(),
+            r#"<path d="M0.1 0.1 0.3 0.2"/>"#
+        );
+    }
+
+    #[test]
+    fn test_compact_override() {
+        let line = Line::new((0.0, 0.0), (10.0, 0.0));
+        let svg = line
+            .to_svg()
+            .with_compact_paths(false)
+            .and(line.to_svg())
+            .with_compact_paths(true);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<path d="M 0.0 0.0 L 10.0 0.0"/><path d="M0 0H10"/>"#
+        );
+    }
+
+    #[test]
+    fn test_projected_viewbox() {
+        let point_result = Point::new(4_512_345.5, 5_400_000.25).to_svg().to_string();
+        assert_eq!(
+            point_result,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="xMidYMid meet" viewBox="4512343.5 5399998.25 4 4"><circle cx="4512345.5" cy="5400000.25" r="1"/></svg>"#
+        )
+    }
+
+    #[test]
+    fn test_dashed_line() {
+        let line_result = Line::new((0.0, 0.0), (10.0, 0.0))
+            .to_svg()
+            .with_stroke_color(Color::Named("black"))
+            .with_stroke_dasharray(vec![4.0, 2.5])
+            .with_stroke_dashoffset(1.0)
+            .with_stroke_linecap(LineCap::Round)
+            .with_stroke_linejoin(LineJoin::Bevel)
+            .with_stroke_miterlimit(2.0)
+            .svg_str();
+        assert_eq!(
+            line_result,
+            r#"<path d="M 0.0 0.0 L 10.0 0.0" stroke="black" stroke-dasharray="4,2.5" stroke-dashoffset="1" stroke-linecap="round" stroke-linejoin="bevel" stroke-miterlimit="2"/>"#
+        )
+    }
+
+    #[test]
+    fn test_fill_rule() {
+        let triangle = Triangle::new((0, 0).into(), (10, 0).into(), (0, 10).into());
+        assert_eq!(
+            triangle
+                .to_svg()
+                .with_fill_rule(FillRule::NonZero)
+                .svg_str(),
+            r#"<path d="M 0 0 L 10 0 L 0 10 L 0 0 Z" fill-rule="nonzero"/>"#
+        );
+        assert_eq!(
+            triangle.to_svg().with_fill_rule(FillRule::Unset).svg_str(),
+            r#"<path d="M 0 0 L 10 0 L 0 10 L 0 0 Z"/>"#
+        );
+        let svg = triangle
+            .to_svg()
+            .with_fill_rule(FillRule::EvenOdd)
+            .and(triangle.to_svg())
+            .with_fill_rule(FillRule::NonZero);
+        assert_eq!(
+            svg.svg_str(),
+            concat!(
+                r#"<path d="M 0 0 L 10 0 L 0 10 L 0 0 Z" fill-rule="evenodd"/>"#,
+                r#"<path d="M 0 0 L 10 0 L 0 10 L 0 0 Z" fill-rule="nonzero"/>"#,
+            )
+        );
+        let svg = triangle
+            .to_svg()
+            .and(triangle.to_svg())
+            .with_fill_rule(FillRule::NonZero)
+            .with_group(true);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<g fill-rule="nonzero"><path d="M 0 0 L 10 0 L 0 10 L 0 0 Z"/><path d="M 0 0 L 10 0 L 0 10 L 0 0 Z"/></g>"#
+        );
+        let svg = triangle
+            .to_svg()
+            .with_fill_rule(FillRule::NonZero)
+            .with_style_classes(true);
+        assert_eq!(
+            svg.svg_str(),
+            r#"<style>.s0{fill-rule:nonzero}</style><path d="M 0 0 L 10 0 L 0 10 L 0 0 Z" class="s0"/>"#
+        );
+    }
 }
@@ -0,0 +1,309 @@
+use crate::{precision::DisplayNumber, svg::Escaped, Precision, RenderContext, Style, ViewBox};
+use std::fmt::{Display, Formatter, Result};
+
+/// Shape drawn for points, fitting in the circle of the point radius.
+///
+/// Shapes other than circles are defined once per radius as `<symbol>` elements in the
+/// `<defs>` of the document and drawn with `<use>` elements, e.g. to tell point layers apart
+/// without colors:
+///
+/// ```
+/// # use geo::Point;
+/// # use geo_svg::{PointSymbol, ToSvg};
+/// let well = Point::new(10.0, 20.0);
+/// let svg = well
+///     .to_svg()
+///     .with_point_symbol(PointSymbol::Square)
+///     .with_radius(2.0);
+/// assert_eq!(
+///     svg.svg_str(),
+///     concat!(
+///         r##"<defs><symbol id="symbol-square-2" overflow="visible">"##,
+///         r##"<path d="M -2 -2 L 2 -2 L 2 2 L -2 2 Z"/></symbol></defs>"##,
+///         r##"<use href="#symbol-square-2" x="10.0" y="20.0"/>"##,
+///     )
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointSymbol {
+    Circle,
+    Square,
+    /// triangle pointing up
+    Triangle,
+    Diamond,
+    /// plus sign
+    Cross,
+    /// diagonal cross
+    X,
+    /// five-pointed star
+    Star,
+    /// path data of a shape centered on the point, in document units with the y axis pointing
+    /// down, ignoring the radius; it's flipped upright in documents flipping the y axis
+    Custom {
+        id: String,
+        path: String,
+    },
+}
+
+impl PointSymbol {
+    /// custom symbol with the given `id` and path data, centered on the point
+    pub fn custom(id: impl Into<String>, path: impl Into<String>) -> Self {
+        PointSymbol::Custom {
+            id: id.into(),
+            path: path.into(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            PointSymbol::Circle => "circle",
+            PointSymbol::Square => "square",
+            PointSymbol::Triangle => "triangle",
+            PointSymbol::Diamond => "diamond",
+            PointSymbol::Cross => "cross",
+            PointSymbol::X => "x",
+            PointSymbol::Star => "star",
+            PointSymbol::Custom { id, .. } => id,
+        }
+    }
+
+    /// vertices of the shape with a radius of 1, with the y axis pointing down
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        let polar = |angle: f64, radius: f64| {
+            let angle = angle.to_radians();
+            (radius * angle.sin(), -radius * angle.cos())
+        };
+        let width = 1.0 / 3.0;
+        let plus = vec![
+            (-width, -1.0),
+            (width, -1.0),
+            (width, -width),
+            (1.0, -width),
+            (1.0, width),
+            (width, width),
+            (width, 1.0),
+            (-width, 1.0),
+            (-width, width),
+            (-1.0, width),
+            (-1.0, -width),
+            (-width, -width),
+        ];
+        match self {
+            PointSymbol::Square => vec![(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)],
+            PointSymbol::Triangle => (0..3).map(|i| polar(i as f64 * 120.0, 1.0)).collect(),
+            PointSymbol::Diamond => vec![(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)],
+            PointSymbol::Cross => plus,
+            PointSymbol::X => {
+                // the plus sign rotated by 45 degrees, with its arms reaching the circle
+                let scale = std::f64::consts::FRAC_1_SQRT_2;
+                plus.into_iter()
+                    .map(|(x, y)| ((x - y) * scale, (x + y) * scale))
+                    .collect()
+            }
+            PointSymbol::Star => (0..10)
+                .map(|i| polar(i as f64 * 36.0, if i % 2 == 0 { 1.0 } else { 0.382 }))
+                .collect(),
+            PointSymbol::Circle | PointSymbol::Custom { .. } => vec![],
+        }
+    }
+}
+
+/// Path data of a [`PointSymbol`] centered on `center`.
+pub(crate) struct SymbolPath<'a> {
+    pub symbol: &'a PointSymbol,
+    pub center: (f64, f64),
+    pub radius: f64,
+    /// whether to flip the shape, to draw it upright in documents flipping the y axis; custom
+    /// path data is written as is and needs a `transform` instead
+    pub flip_y: bool,
+    /// precision of the vertices, [`Precision::Auto`] relative to the size of the shape when
+    /// `None`, since the vertices of most shapes are irrational
+    pub precision: Option<Precision>,
+}
+
+impl Display for SymbolPath<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        if let PointSymbol::Custom { path, .. } = self.symbol {
+            return write!(fmt, "{}", Escaped(path));
+        }
+        let flip = if self.flip_y { -1.0 } else { 1.0 };
+        let diameter = 2.0 * self.radius;
+        let precision = self.precision.unwrap_or_else(|| {
+            Precision::Auto.resolve(&ViewBox::new(0.0, 0.0, diameter, diameter))
+        });
+        for (i, (x, y)) in self.symbol.vertices().into_iter().enumerate() {
+            write!(
+                fmt,
+                "{command} {x} {y} ",
+                command = if i == 0 { "M" } else { "L" },
+                x = DisplayNumber(self.center.0 + x * self.radius, Some(precision)),
+                y = DisplayNumber(self.center.1 + y * self.radius * flip, Some(precision)),
+            )?;
+        }
+        fmt.write_str("Z")
+    }
+}
+
+/// Point symbol of a style, defined in the `<defs>` of the documents using it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    symbol: PointSymbol,
+    radius: f32,
+    y_up: bool,
+    precision: Option<Precision>,
+}
+
+impl Symbol {
+    /// the symbol points are drawn with in `style` and the `render` context, unless they're
+    /// drawn as circles
+    pub(crate) fn for_style(style: &Style, render: &RenderContext) -> Option<Symbol> {
+        match style.point_symbol.as_ref()? {
+            PointSymbol::Circle => None,
+            symbol => Some(Symbol {
+                symbol: symbol.clone(),
+                radius: style.point_radius(render),
+                y_up: render.y_up,
+                precision: style.precision,
+            }),
+        }
+    }
+
+    /// this custom symbol with the given `id`, or `None` for built-in symbols, whose ids are
+    /// derived from their shape
+    pub(crate) fn with_id(&self, id: String) -> Option<Symbol> {
+        match &self.symbol {
+            PointSymbol::Custom { path, .. } => Some(Symbol {
+                symbol: PointSymbol::Custom {
+                    id,
+                    path: path.clone(),
+                },
+                ..self.clone()
+            }),
+            _ => None,
+        }
+    }
+
+    /// id of the definition of this symbol, the same for symbols of equal shapes and radii
+    pub fn id(&self) -> String {
+        match &self.symbol {
+            PointSymbol::Custom { id, .. } => id.clone(),
+            symbol => format!(
+                "symbol-{}-{}",
+                symbol.name(),
+                self.radius.to_string().replace(['.', '-'], "_")
+            ),
+        }
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        let path = SymbolPath {
+            symbol: &self.symbol,
+            center: (0.0, 0.0),
+            radius: self.radius as f64,
+            flip_y: self.y_up,
+            precision: self.precision,
+        };
+        let transform = match self.symbol {
+            PointSymbol::Custom { .. } if self.y_up => r#" transform="scale(1,-1)""#,
+            _ => "",
+        };
+        write!(
+            fmt,
+            r#"<symbol id="{id}" overflow="visible"><path d="{path}"{transform}/></symbol>"#,
+            id = Escaped(&self.id()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(symbol: PointSymbol, style: Style, render: RenderContext) -> Symbol {
+        Symbol::for_style(
+            &Style {
+                point_symbol: Some(symbol),
+                ..style
+            },
+            &render,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_shapes() {
+        let style = Style {
+            precision: Some(Precision::Decimals(2)),
+            ..Style::default()
+        };
+        assert_eq!(
+            symbol(
+                PointSymbol::Triangle,
+                style.clone(),
+                RenderContext::default()
+            )
+            .to_string(),
+            r#"<symbol id="symbol-triangle-1" overflow="visible"><path d="M 0 -1 L 0.87 0.5 L -0.87 0.5 Z"/></symbol>"#
+        );
+        assert_eq!(
+            symbol(PointSymbol::X, style.clone(), RenderContext::default()).to_string(),
+            r#"<symbol id="symbol-x-1" overflow="visible"><path d="M 0.47 -0.94 L 0.94 -0.47 L 0.47 0 L 0.94 0.47 L 0.47 0.94 L 0 0.47 L -0.47 0.94 L -0.94 0.47 L -0.47 0 L -0.94 -0.47 L -0.47 -0.94 L 0 -0.47 Z"/></symbol>"#
+        );
+        let flipped = RenderContext {
+            y_up: true,
+            ..RenderContext::default()
+        };
+        let style = Style {
+            radius: Some(2.5),
+            ..style
+        };
+        assert_eq!(
+            symbol(PointSymbol::Diamond, style, flipped).to_string(),
+            r#"<symbol id="symbol-diamond-2_5" overflow="visible"><path d="M 0 2.5 L 2.5 0 L 0 -2.5 L -2.5 0 Z"/></symbol>"#
+        );
+        assert_eq!(
+            Symbol::for_style(&Style::default(), &RenderContext::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_auto_precision() {
+        assert_eq!(
+            symbol(
+                PointSymbol::Triangle,
+                Style::default(),
+                RenderContext::default()
+            )
+            .to_string(),
+            r#"<symbol id="symbol-triangle-1" overflow="visible"><path d="M 0 -1 L 0.866 0.5 L -0.866 0.5 Z"/></symbol>"#
+        );
+        let style = Style {
+            radius: Some(200.0),
+            ..Style::default()
+        };
+        assert_eq!(
+            symbol(PointSymbol::Triangle, style, RenderContext::default()).to_string(),
+            r#"<symbol id="symbol-triangle-200" overflow="visible"><path d="M 0 -200 L 173.21 100 L -173.21 100 Z"/></symbol>"#
+        );
+    }
+
+    #[test]
+    fn test_custom() {
+        let tree = PointSymbol::custom("tree", "M 0 -3 L 2 1 L -2 1 Z");
+        assert_eq!(
+            symbol(tree.clone(), Style::default(), RenderContext::default()).to_string(),
+            r#"<symbol id="tree" overflow="visible"><path d="M 0 -3 L 2 1 L -2 1 Z"/></symbol>"#
+        );
+        let flipped = RenderContext {
+            y_up: true,
+            ..RenderContext::default()
+        };
+        assert_eq!(
+            symbol(tree, Style::default(), flipped).to_string(),
+            r#"<symbol id="tree" overflow="visible"><path d="M 0 -3 L 2 1 L -2 1 Z" transform="scale(1,-1)"/></symbol>"#
+        );
+    }
+}
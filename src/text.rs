@@ -1,12 +1,11 @@
-use std::fmt::Display;
+use std::fmt::{Display, Result, Write};
 
 use geo::{Coord, CoordNum};
+use num_traits::NumCast;
 
-use crate::{Style, ToSvgStr, ViewBox};
-
-// 🚧🚧 TODO 🚧🚧 : Implement this to find visual center of polygons to place text automatically
-//
-// https://blog.mapbox.com/a-new-algorithm-for-finding-a-visual-center-of-a-polygon-7c77e6492fbc
+use crate::{
+    precision::DisplayNumber, Color, RenderContext, Style, ToSvgStr, ViewBox, VisualCenter,
+};
 
 /// Simple Text element for SVGs. This comes in handy if you want to enumerate some sort of
 /// geometry for any purposes
@@ -21,6 +20,10 @@ where
     position: Coord<C>,
     /// the size of the font of the text
     font_size: f32,
+    /// the color of the text, black when `None`
+    fill: Option<Color>,
+    /// whether the text is centered on its position instead of starting there
+    centered: bool,
 }
 
 impl<S, C> Text<S, C>
@@ -34,13 +37,68 @@ where
             text,
             position,
             font_size: 10.0,
+            fill: None,
+            centered: false,
         }
     }
 
+    /// create new Text object centered on the visual center of `shape`, the point inside it
+    /// farthest from its outline, or `None` if `shape` is empty
+    ///
+    /// ```
+    /// # use geo::polygon;
+    /// # use geo_svg::{Text, ToSvg};
+    /// let horseshoe = polygon![
+    ///     (x: 0.0, y: 0.0),
+    ///     (x: 30.0, y: 0.0),
+    ///     (x: 30.0, y: 30.0),
+    ///     (x: 20.0, y: 30.0),
+    ///     (x: 20.0, y: 10.0),
+    ///     (x: 10.0, y: 10.0),
+    ///     (x: 10.0, y: 30.0),
+    ///     (x: 0.0, y: 30.0),
+    /// ];
+    /// // in a corner of the U, where it's the widest, while its centroid lies in the notch
+    /// let label = Text::at_visual_center("horseshoe", &horseshoe).unwrap();
+    /// assert_eq!(
+    ///     label.to_svg().svg_str(),
+    ///     r#"<text font-size="10" text-anchor="middle" dominant-baseline="central" x="5.859375" y="5.859375">horseshoe</text>"#
+    /// );
+    /// ```
+    pub fn at_visual_center(text: S, shape: &impl VisualCenter<C>) -> Option<Self> {
+        Some(Self {
+            centered: true,
+            ..Self::new(text, shape.default_visual_center()?)
+        })
+    }
+
+    /// like [`at_visual_center`], finding the visual center to within `precision` in document
+    /// units
+    ///
+    /// [`at_visual_center`]: Text::at_visual_center
+    pub fn at_visual_center_with_precision(
+        text: S,
+        shape: &impl VisualCenter<C>,
+        precision: f64,
+    ) -> Option<Self> {
+        Some(Self {
+            centered: true,
+            ..Self::new(text, shape.visual_center(precision)?)
+        })
+    }
+
     /// overwrite the existing font size
     pub fn with_font_size(self, font_size: f32) -> Self {
         Self { font_size, ..self }
     }
+
+    /// set the color of the text, e.g. [`Color::readable_text_color`] of the fill it's drawn on
+    pub fn with_fill_color(self, fill: Color) -> Self {
+        Self {
+            fill: Some(fill),
+            ..self
+        }
+    }
 }
 
 impl<S, C> ToSvgStr for Text<S, C>
@@ -48,20 +106,43 @@ where
     S: Display,
     C: CoordNum + std::fmt::Display,
 {
-    fn to_svg_str(&self, _style: &Style) -> String {
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result {
         let Text {
             text,
             position: Coord { x, y },
             font_size,
+            fill,
+            centered,
         } = self;
-        format!(r#"<text font-size="{font_size}" x="{x}" y="{y}">{text}</text>"#)
+        let x = DisplayNumber(*x, style.precision);
+        let fill = Fill(*fill);
+        let anchor = if *centered {
+            r#" text-anchor="middle" dominant-baseline="central""#
+        } else {
+            ""
+        };
+        if render.y_up {
+            // flip the text back inside the flipped document so it isn't mirrored
+            let y: f64 = NumCast::from(*y).unwrap_or(0.0);
+            let y = DisplayNumber(-y, style.precision);
+            write!(
+                writer,
+                r#"<text font-size="{font_size}"{fill}{anchor} x="{x}" y="{y}" transform="scale(1,-1)">{text}</text>"#
+            )
+        } else {
+            let y = DisplayNumber(*y, style.precision);
+            write!(
+                writer,
+                r#"<text font-size="{font_size}"{fill}{anchor} x="{x}" y="{y}">{text}</text>"#
+            )
+        }
     }
 
     // we can probably do better here by calculating a viewbox based on font and font size
     // something along the lines of
     //
     // https://stackoverflow.com/questions/71283347/difference-in-length-calculation-for-svg-text-element
-    fn viewbox(&self, _style: &Style) -> ViewBox {
+    fn viewbox(&self, _style: &Style, _render: &RenderContext) -> ViewBox {
         ViewBox {
             min_x: None,
             min_y: None,
@@ -70,3 +151,15 @@ where
         }
     }
 }
+
+/// `fill` attribute of a text, if it has a color.
+struct Fill(Option<Color>);
+
+impl Display for Fill {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result {
+        match self.0 {
+            Some(fill) => write!(fmt, r#" fill="{fill}""#),
+            None => Ok(()),
+        }
+    }
+}
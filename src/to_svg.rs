@@ -1,16 +1,25 @@
-use crate::{Style, Svg, ToSvgStr, ViewBox};
+use crate::{OwnedSvg, Svg, ToSvgStr};
 
 pub trait ToSvg {
-    fn to_svg(&self) -> Svg;
+    fn to_svg(&self) -> Svg<'_>;
 }
 
 impl<T: ToSvgStr> ToSvg for T {
-    fn to_svg(&self) -> Svg {
+    fn to_svg(&self) -> Svg<'_> {
         Svg {
             items: vec![self],
-            siblings: vec![],
-            viewbox: ViewBox::default(),
-            style: Style::default(),
+            ..Default::default()
         }
     }
 }
+
+/// Like [`ToSvg`] but moves `self` into an [`OwnedSvg`] that doesn't borrow from anything.
+pub trait IntoOwnedSvg {
+    fn into_owned_svg(self) -> OwnedSvg;
+}
+
+impl<T: ToSvgStr + Send + Sync + 'static> IntoOwnedSvg for T {
+    fn into_owned_svg(self) -> OwnedSvg {
+        OwnedSvg::new(self)
+    }
+}
@@ -1,16 +1,46 @@
-use crate::{Style, Svg, ViewBox};
+use crate::{RenderContext, Style, SvgDocument, ViewBox};
+use std::fmt::{Result, Write};
+use std::ops::Deref;
 
+/// Renders an item to SVG elements.
+///
+/// Implementors write their elements in [`write_svg`], so large items can be streamed without
+/// building intermediate strings; [`to_svg_str`] collects them into a string.
+///
+/// [`write_svg`]: ToSvgStr::write_svg
+/// [`to_svg_str`]: ToSvgStr::to_svg_str
 pub trait ToSvgStr {
-    fn to_svg_str(&self, style: &Style) -> String;
-    fn viewbox(&self, style: &Style) -> ViewBox;
+    /// write the SVG elements of this item to `writer`, with `style` inside the document
+    /// described by `render`
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, render: &RenderContext) -> Result;
+
+    /// render the SVG elements of this item to a new string
+    fn to_svg_str(&self, style: &Style, render: &RenderContext) -> String {
+        let mut svg_str = String::new();
+        self.write_svg(&mut svg_str, style, render)
+            .expect("writing to a String can't fail");
+        svg_str
+    }
+
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox;
 }
 
-impl<'a> ToSvgStr for Svg<'a> {
-    fn to_svg_str(&self, style: &Style) -> String {
-        self.clone().with_style(style).to_string()
+impl<I> ToSvgStr for SvgDocument<I>
+where
+    I: Deref + Clone,
+    I::Target: ToSvgStr,
+{
+    /// the nested document is rendered on its own, with its own render context
+    fn write_svg(&self, writer: &mut dyn Write, style: &Style, _render: &RenderContext) -> Result {
+        let style = self.style.inherit(style);
+        write!(writer, "{}", self.clone().with_style(&style))
     }
 
-    fn viewbox(&self, style: &Style) -> ViewBox {
-        self.clone().with_style(style).viewbox
+    fn viewbox(&self, style: &Style, render: &RenderContext) -> ViewBox {
+        let render = RenderContext {
+            y_up: self.y_up,
+            ..*render
+        };
+        self.viewbox_with(style, &render)
     }
 }
@@ -1,13 +1,13 @@
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct ViewBox {
-    pub min_x: Option<f32>,
-    pub min_y: Option<f32>,
-    pub max_x: Option<f32>,
-    pub max_y: Option<f32>,
+    pub min_x: Option<f64>,
+    pub min_y: Option<f64>,
+    pub max_x: Option<f64>,
+    pub max_y: Option<f64>,
 }
 
 impl ViewBox {
-    pub fn new(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
         Self {
             min_x: Some(min_x),
             min_y: Some(min_y),
@@ -25,31 +25,31 @@ impl ViewBox {
         }
     }
 
-    pub fn min_x(&self) -> f32 {
+    pub fn min_x(&self) -> f64 {
         self.min_x.unwrap_or_default()
     }
 
-    pub fn min_y(&self) -> f32 {
+    pub fn min_y(&self) -> f64 {
         self.min_y.unwrap_or_default()
     }
 
-    pub fn max_x(&self) -> f32 {
+    pub fn max_x(&self) -> f64 {
         self.max_x.unwrap_or_default()
     }
 
-    pub fn max_y(&self) -> f32 {
+    pub fn max_y(&self) -> f64 {
         self.max_y.unwrap_or_default()
     }
 
-    pub fn width(&self) -> f32 {
+    pub fn width(&self) -> f64 {
         (self.min_x() - self.max_x()).abs()
     }
 
-    pub fn height(&self) -> f32 {
+    pub fn height(&self) -> f64 {
         (self.min_y() - self.max_y()).abs()
     }
 
-    fn min_option(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    fn min_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
         match (a, b) {
             (Some(a), Some(b)) => Some(a.min(b)),
             (Some(a), None) => Some(a),
@@ -58,7 +58,7 @@ impl ViewBox {
         }
     }
 
-    fn max_option(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    fn max_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
         match (a, b) {
             (Some(a), Some(b)) => Some(a.max(b)),
             (Some(a), None) => Some(a),
@@ -67,7 +67,17 @@ impl ViewBox {
         }
     }
 
-    pub fn with_margin(mut self, margin: f32) -> Self {
+    /// mirror this viewbox along the x axis, as done to the content of documents with a y axis
+    /// pointing up
+    pub fn flip_y(&self) -> Self {
+        Self {
+            min_y: self.max_y.map(|y| -y),
+            max_y: self.min_y.map(|y| -y),
+            ..*self
+        }
+    }
+
+    pub fn with_margin(mut self, margin: f64) -> Self {
         self.min_x = self.min_x.map(|x| x - margin);
         self.min_y = self.min_y.map(|y| y - margin);
         self.max_x = self.max_x.map(|x| x + margin);
@@ -0,0 +1,191 @@
+use crate::{Precision, ViewBox};
+use std::fmt::{Display, Formatter, Result};
+
+/// Length with a unit, as used for the `width` and `height` of the root `<svg>` element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Mm(f64),
+    Cm(f64),
+    In(f64),
+    Pt(f64),
+    Percent(f64),
+}
+
+impl Length {
+    /// the same length with its value multiplied by `factor`
+    pub fn scale(self, factor: f64) -> Self {
+        self.map(|value| value * factor)
+    }
+
+    /// the same length with `f` applied to its value
+    fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        match self {
+            Length::Px(value) => Length::Px(f(value)),
+            Length::Mm(value) => Length::Mm(f(value)),
+            Length::Cm(value) => Length::Cm(f(value)),
+            Length::In(value) => Length::In(f(value)),
+            Length::Pt(value) => Length::Pt(f(value)),
+            Length::Percent(value) => Length::Percent(f(value)),
+        }
+    }
+
+    /// this length in CSS pixels, at 96 pixels per inch, unless it's a percentage
+    pub fn pixels(self) -> Option<f64> {
+        match self {
+            Length::Px(value) => Some(value),
+            Length::Mm(value) => Some(value * 96.0 / 25.4),
+            Length::Cm(value) => Some(value * 96.0 / 2.54),
+            Length::In(value) => Some(value * 96.0),
+            Length::Pt(value) => Some(value * 96.0 / 72.0),
+            Length::Percent(_) => None,
+        }
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Length::Px(value) => write!(fmt, "{}px", value),
+            Length::Mm(value) => write!(fmt, "{}mm", value),
+            Length::Cm(value) => write!(fmt, "{}cm", value),
+            Length::In(value) => write!(fmt, "{}in", value),
+            Length::Pt(value) => write!(fmt, "{}pt", value),
+            Length::Percent(value) => write!(fmt, "{}%", value),
+        }
+    }
+}
+
+/// Output size of a document.
+///
+/// Percentages are relative to the container of the document rather than to the other side,
+/// so a side derived from a percentage can't follow the aspect ratio of the viewbox: it's set to
+/// `100%` instead, and the viewbox is fitted in the resulting viewport according to the
+/// [`PreserveAspectRatio`] of the document. Derived lengths are rounded to 2 decimals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Size {
+    /// explicit width and height
+    Exact(Length, Length),
+    /// explicit width, the height follows the aspect ratio of the viewbox
+    Width(Length),
+    /// explicit height, the width follows the aspect ratio of the viewbox
+    Height(Length),
+    /// the larger side of the viewbox measures the given length, the other one follows the
+    /// aspect ratio of the viewbox
+    Fit(Length),
+}
+
+impl Size {
+    /// width and height of a document showing `viewbox`
+    pub fn resolve(&self, viewbox: &ViewBox) -> (Length, Length) {
+        let (width, height) = (viewbox.width(), viewbox.height());
+        let ratio = |a: f64, b: f64| if b > 0.0 { a / b } else { 1.0 };
+        let derive = |length: Length, ratio: f64| match length {
+            Length::Percent(_) => Length::Percent(100.0),
+            length => length.scale(ratio).map(|value| {
+                Precision::Decimals(2)
+                    .round(value)
+                    .map_or(value, |(value, _)| value)
+            }),
+        };
+        match *self {
+            Size::Exact(width, height) => (width, height),
+            Size::Width(length) => (length, derive(length, ratio(height, width))),
+            Size::Height(length) => (derive(length, ratio(width, height)), length),
+            Size::Fit(length) if width >= height => (length, derive(length, ratio(height, width))),
+            Size::Fit(length) => (derive(length, ratio(width, height)), length),
+        }
+    }
+}
+
+/// Alignment of the viewbox along one axis of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Min,
+    Mid,
+    Max,
+}
+
+impl Display for Align {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            Align::Min => write!(fmt, "Min"),
+            Align::Mid => write!(fmt, "Mid"),
+            Align::Max => write!(fmt, "Max"),
+        }
+    }
+}
+
+/// How the viewbox is fitted in a viewport of a different aspect ratio, written as the
+/// `preserveAspectRatio` attribute of the root `<svg>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreserveAspectRatio {
+    /// stretch the viewbox to fill the viewport
+    None,
+    /// scale the viewbox uniformly so it's entirely visible, aligned along x and y
+    Meet(Align, Align),
+    /// scale the viewbox uniformly so it covers the whole viewport, aligned along x and y
+    Slice(Align, Align),
+}
+
+impl Default for PreserveAspectRatio {
+    fn default() -> Self {
+        PreserveAspectRatio::Meet(Align::Mid, Align::Mid)
+    }
+}
+
+impl Display for PreserveAspectRatio {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            PreserveAspectRatio::None => write!(fmt, "none"),
+            PreserveAspectRatio::Meet(x, y) => write!(fmt, "x{}Y{} meet", x, y),
+            PreserveAspectRatio::Slice(x, y) => write!(fmt, "x{}Y{} slice", x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let viewbox = ViewBox::new(0.0, 0.0, 200.0, 100.0);
+        assert_eq!(
+            Size::Fit(Length::Px(800.0)).resolve(&viewbox),
+            (Length::Px(800.0), Length::Px(400.0))
+        );
+        let viewbox = ViewBox::new(0.0, 0.0, 100.0, 200.0);
+        assert_eq!(
+            Size::Fit(Length::Mm(100.0)).resolve(&viewbox),
+            (Length::Mm(50.0), Length::Mm(100.0))
+        );
+    }
+
+    #[test]
+    fn test_derived_lengths() {
+        let viewbox = ViewBox::new(0.0, 0.0, 70.0, 20.0);
+        assert_eq!(
+            Size::Width(Length::Mm(100.0)).resolve(&viewbox),
+            (Length::Mm(100.0), Length::Mm(28.57))
+        );
+        assert_eq!(
+            Size::Width(Length::Percent(100.0)).resolve(&viewbox),
+            (Length::Percent(100.0), Length::Percent(100.0))
+        );
+        assert_eq!(
+            Size::Fit(Length::Percent(50.0)).resolve(&viewbox),
+            (Length::Percent(50.0), Length::Percent(100.0))
+        );
+    }
+
+    #[test]
+    fn test_preserve_aspect_ratio() {
+        assert_eq!(PreserveAspectRatio::default().to_string(), "xMidYMid meet");
+        assert_eq!(
+            PreserveAspectRatio::Slice(Align::Min, Align::Max).to_string(),
+            "xMinYMax slice"
+        );
+        assert_eq!(PreserveAspectRatio::None.to_string(), "none");
+    }
+}